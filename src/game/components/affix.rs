@@ -0,0 +1,126 @@
+use crate::game::{
+    components::{damage::Damage, health::Health, projectile::Projectile},
+    utils::magic::spell_tag::SpellTagEffect,
+};
+use hecs::{Entity, World};
+use micro_games_kit::third_party::serde::Deserialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize)]
+#[serde(
+    crate = "micro_games_kit::third_party::serde",
+    rename_all = "snake_case"
+)]
+pub enum Affix {
+    Shielded,
+    Hasted,
+    Vampiric,
+    Teleporting,
+    Reflective,
+    Unstable,
+}
+
+impl Affix {
+    pub const ALL: [Self; 6] = [
+        Self::Shielded,
+        Self::Hasted,
+        Self::Vampiric,
+        Self::Teleporting,
+        Self::Reflective,
+        Self::Unstable,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Shielded => "Shielded",
+            Self::Hasted => "Hasted",
+            Self::Vampiric => "Vampiric",
+            Self::Teleporting => "Teleporting",
+            Self::Reflective => "Reflective",
+            Self::Unstable => "Elementally Unstable",
+        }
+    }
+}
+
+/// Enemy with rolled affixes, each affix also adds its own component.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Elite {
+    pub affixes: Vec<Affix>,
+}
+
+impl Elite {
+    pub fn title(&self) -> String {
+        self.affixes
+            .iter()
+            .map(|affix| affix.name())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// Absorbs damage before it reaches health.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Shielded {
+    pub value: f32,
+}
+
+/// Multiplies speed limit and acceleration.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hasted {
+    pub multiplier: f32,
+}
+
+/// Heals by fraction of damage dealt on contact.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Vampiric {
+    pub fraction: f32,
+}
+
+/// Blinks next to the player every `interval` seconds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Teleporting {
+    pub interval: f32,
+    pub distance: f32,
+    pub time_left: f32,
+}
+
+/// Sends projectiles back where they came from.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Reflective;
+
+impl Reflective {
+    /// Tells if one of entities is a projectile bouncing off the other one
+    /// instead of affecting it. Only projectiles that could hurt the entity
+    /// bounce, allies' spells pass through as usual.
+    pub fn reflects(world: &World, entity_a: Entity, entity_b: Entity) -> bool {
+        let harmful = |projectile: Entity, entity: Entity| match (
+            world.get::<&Damage>(projectile),
+            world.get::<&Health>(entity),
+        ) {
+            (Ok(damage), Ok(health)) => damage.layer.allow_damage(health.layer),
+            _ => true,
+        };
+        let reflects = |projectile: Entity, entity: Entity| {
+            world.get::<&Reflective>(entity).is_ok()
+                && world.get::<&Projectile>(projectile).is_ok()
+                && harmful(projectile, entity)
+        };
+        reflects(entity_a, entity_b) || reflects(entity_b, entity_a)
+    }
+}
+
+/// Switches its element every `interval` seconds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Unstable {
+    pub interval: f32,
+    pub time_left: f32,
+}
+
+impl Unstable {
+    pub fn next_effect(effect: SpellTagEffect) -> SpellTagEffect {
+        match effect {
+            SpellTagEffect::None | SpellTagEffect::Electric => SpellTagEffect::Fire,
+            SpellTagEffect::Fire => SpellTagEffect::Water,
+            SpellTagEffect::Water => SpellTagEffect::Electric,
+        }
+    }
+}
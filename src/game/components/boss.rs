@@ -0,0 +1,72 @@
+use crate::game::components::spell_loadout::EnemySpell;
+use micro_games_kit::third_party::serde::Deserialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(
+    crate = "micro_games_kit::third_party::serde",
+    tag = "pattern",
+    rename_all = "snake_case"
+)]
+pub enum BossPattern {
+    /// Spells spread evenly all around the boss.
+    Ring { count: usize },
+    /// Wall spell thrown at the player.
+    Wall,
+    /// Fan of spells aimed at the player that follow them.
+    Homing {
+        count: usize,
+        /// Angle between neighbour spells, in degrees.
+        spread: f32,
+        /// Radians per second.
+        turn_rate: f32,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(crate = "micro_games_kit::third_party::serde")]
+pub struct BossAttack {
+    #[serde(flatten)]
+    pub pattern: BossPattern,
+    #[serde(default)]
+    pub spell: EnemySpell,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(crate = "micro_games_kit::third_party::serde")]
+pub struct BossPhase {
+    /// Phase starts once health fraction drops to this value.
+    pub health: f32,
+    /// Seconds between attacks.
+    pub interval: f32,
+    /// Attacks used in order, in a loop.
+    pub attacks: Vec<BossAttack>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Boss {
+    pub title: String,
+    pub phases: Vec<BossPhase>,
+    pub phase: usize,
+    pub attack: usize,
+    pub cooldown: f32,
+}
+
+impl Boss {
+    pub fn new(title: impl ToString, phases: Vec<BossPhase>) -> Self {
+        Self {
+            title: title.to_string(),
+            phases,
+            phase: 0,
+            attack: 0,
+            cooldown: 0.0,
+        }
+    }
+
+    /// Index of phase for given health fraction - last phase whose threshold was reached.
+    pub fn phase_for_health(&self, health: f32) -> usize {
+        self.phases
+            .iter()
+            .rposition(|phase| health <= phase.health)
+            .unwrap_or_default()
+    }
+}
@@ -0,0 +1,88 @@
+use crate::game::{
+    components::spell::Spell,
+    utils::magic::spell_tag::{SpellTagEffect, SpellTagShape, SpellTagTarget},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuffKind {
+    /// Restores health over time.
+    Heal,
+    /// Speeds movement up.
+    Haste,
+    /// Absorbs damage before it reaches health.
+    Shield,
+}
+
+impl BuffKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Heal => "Heal",
+            Self::Haste => "Haste",
+            Self::Shield => "Shield",
+        }
+    }
+
+    pub fn icon(&self) -> &'static str {
+        match self {
+            Self::Heal => "ui/buff/heal",
+            Self::Haste => "ui/buff/haste",
+            Self::Shield => "ui/buff/shield",
+        }
+    }
+}
+
+/// Temporary effect of a spell the caster has cast on itself.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Buff {
+    pub kind: BuffKind,
+    /// Health per second for heal, speed multiplier for haste and absorbed
+    /// damage for shield.
+    pub value: f32,
+    pub duration: f32,
+    pub time_left: f32,
+}
+
+impl Buff {
+    /// Buffs last this many times longer than duration of spells.
+    pub const DURATION_MULTIPLIER: f32 = 3.0;
+
+    /// Buff given by spell cast on oneself: wall shields, water heals and
+    /// electricity hastes. `None` for spells that do not buff.
+    pub fn from_spell(spell: &Spell) -> Option<Self> {
+        if spell.target != SpellTagTarget::Oneself {
+            return None;
+        }
+        let duration = spell.duration.time() * Self::DURATION_MULTIPLIER;
+        let strength = spell.size.radius();
+        let (kind, value) = match (spell.shape, spell.effect) {
+            (SpellTagShape::Wall, _) => (BuffKind::Shield, strength),
+            (_, SpellTagEffect::Water) => (BuffKind::Heal, strength / duration),
+            (_, SpellTagEffect::Electric) => (BuffKind::Haste, 1.0 + strength / 80.0),
+            _ => return None,
+        };
+        Some(Self {
+            kind,
+            value,
+            duration,
+            time_left: duration,
+        })
+    }
+}
+
+/// Buffs active on entity, at most one of each kind.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Buffs {
+    pub active: Vec<Buff>,
+}
+
+impl Buffs {
+    pub fn get(&self, kind: BuffKind) -> Option<&Buff> {
+        self.active.iter().find(|buff| buff.kind == kind)
+    }
+
+    pub fn speed_multiplier(&self) -> f32 {
+        self.get(BuffKind::Haste)
+            .map(|buff| buff.value)
+            .unwrap_or(1.0)
+    }
+}
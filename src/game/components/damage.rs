@@ -1,7 +1,10 @@
-#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
 pub enum DamageLayer {
+    #[default]
     None,
     Enemy,
+    /// Player, its summons and their spells.
+    Player,
 }
 
 impl DamageLayer {
@@ -10,12 +13,6 @@ impl DamageLayer {
     }
 }
 
-impl Default for DamageLayer {
-    fn default() -> Self {
-        Self::None
-    }
-}
-
 #[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
 pub struct Damage {
     pub value: f32,
@@ -33,12 +33,13 @@ impl EffectReaction {
         }
     }
 
-    pub fn push_distance(&self) -> f32 {
+    /// Impulse pushing reacting entities apart.
+    pub fn push_impulse(&self) -> f32 {
         match self {
             Self::None => 0.0,
-            Self::Steam => 150.0,
+            Self::Steam => 1200.0,
             Self::Paralize => 0.0,
-            Self::Explode => 10.0,
+            Self::Explode => 400.0,
         }
     }
 }
@@ -52,11 +53,11 @@ pub struct Effect {
 
 impl From<SpellTagEffect> for Effect {
     fn from(value: SpellTagEffect) -> Self {
-        return Effect {
+        Effect {
             electricity: value == SpellTagEffect::Electric,
             fire: value == SpellTagEffect::Fire,
             water: value == SpellTagEffect::Water,
-        };
+        }
     }
 }
 
@@ -66,6 +67,10 @@ impl Effect {
         let water = self.water || other.water;
         let electricity = self.electricity || other.electricity;
 
+        if fire && water && electricity {
+            return EffectReaction::None;
+        }
+
         self.fire = fire;
         self.water = water;
         self.electricity = electricity;
@@ -74,9 +79,7 @@ impl Effect {
         other.water = water;
         other.electricity = electricity;
 
-        if fire && water && electricity {
-            EffectReaction::None
-        } else if fire && water {
+        if fire && water {
             self.fire = false;
             other.fire = false;
             self.water = false;
@@ -113,19 +116,28 @@ impl Effect {
 
     pub fn to_effect_tag(&self) -> SpellTagEffect {
         if self.electricity {
-            return SpellTagEffect::Electric;
+            SpellTagEffect::Electric
         } else if self.fire {
-            return SpellTagEffect::Fire;
+            SpellTagEffect::Fire
         } else if self.water {
-            return SpellTagEffect::Water;
+            SpellTagEffect::Water
+        } else {
+            SpellTagEffect::None
         }
-
-        return SpellTagEffect::None;
     }
 
     pub fn empty(&self) -> bool {
         !self.electricity && !self.fire && !self.water
     }
+
+    pub fn has(&self, effect: SpellTagEffect) -> bool {
+        match effect {
+            SpellTagEffect::None => self.empty(),
+            SpellTagEffect::Fire => self.fire,
+            SpellTagEffect::Water => self.water,
+            SpellTagEffect::Electric => self.electricity,
+        }
+    }
 }
 
 #[cfg(test)]
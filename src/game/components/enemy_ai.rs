@@ -0,0 +1,188 @@
+use crate::game::{components::effect::Effect, utils::magic::spell_tag::SpellTagEffect};
+use micro_games_kit::third_party::serde::Deserialize;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(
+    crate = "micro_games_kit::third_party::serde",
+    rename_all = "snake_case"
+)]
+pub enum EnemyState {
+    Wander,
+    #[default]
+    Chase,
+    Strafe,
+    Retreat,
+    Charge,
+    Cast,
+}
+
+impl EnemyState {
+    /// Multiplier of enemy speed limit and acceleration while in this state.
+    pub fn speed_factor(&self) -> f32 {
+        match self {
+            Self::Wander => 0.5,
+            Self::Chase => 1.0,
+            Self::Strafe => 0.8,
+            Self::Retreat => 0.8,
+            Self::Charge => 2.0,
+            Self::Cast => 0.0,
+        }
+    }
+}
+
+/// What an enemy knows about itself and the player when deciding on a state.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct EnemySenses {
+    pub distance_to_player: f32,
+    /// Health as fraction of its limit.
+    pub health: f32,
+    pub effect: Effect,
+    pub time_in_state: f32,
+    pub cast_ready: bool,
+}
+
+/// All given conditions have to be met, empty condition is always met.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Deserialize)]
+#[serde(crate = "micro_games_kit::third_party::serde", default)]
+pub struct EnemyCondition {
+    pub distance_below: Option<f32>,
+    pub distance_above: Option<f32>,
+    pub health_below: Option<f32>,
+    pub health_above: Option<f32>,
+    pub element: Option<SpellTagEffect>,
+    pub time_in_state_above: Option<f32>,
+    pub cast_ready: Option<bool>,
+}
+
+impl EnemyCondition {
+    pub fn is_met(&self, senses: &EnemySenses) -> bool {
+        self.distance_below
+            .map(|value| senses.distance_to_player < value)
+            .unwrap_or(true)
+            && self
+                .distance_above
+                .map(|value| senses.distance_to_player > value)
+                .unwrap_or(true)
+            && self
+                .health_below
+                .map(|value| senses.health < value)
+                .unwrap_or(true)
+            && self
+                .health_above
+                .map(|value| senses.health > value)
+                .unwrap_or(true)
+            && self
+                .element
+                .map(|value| senses.effect.has(value))
+                .unwrap_or(true)
+            && self
+                .time_in_state_above
+                .map(|value| senses.time_in_state > value)
+                .unwrap_or(true)
+            && self
+                .cast_ready
+                .map(|value| senses.cast_ready == value)
+                .unwrap_or(true)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(crate = "micro_games_kit::third_party::serde")]
+pub struct EnemyTransition {
+    /// `None` means transition from any state.
+    #[serde(default)]
+    pub from: Option<EnemyState>,
+    pub to: EnemyState,
+    #[serde(default)]
+    pub when: EnemyCondition,
+}
+
+/// Behaviour graph - first matching transition out of current state wins.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(crate = "micro_games_kit::third_party::serde")]
+pub struct EnemyBehaviour {
+    #[serde(default)]
+    pub initial: EnemyState,
+    #[serde(default)]
+    pub transitions: Vec<EnemyTransition>,
+}
+
+impl Default for EnemyBehaviour {
+    // chase player and stop to cast whenever spell is ready.
+    fn default() -> Self {
+        Self {
+            initial: EnemyState::Chase,
+            transitions: vec![
+                EnemyTransition {
+                    from: Some(EnemyState::Chase),
+                    to: EnemyState::Cast,
+                    when: EnemyCondition {
+                        cast_ready: Some(true),
+                        ..Default::default()
+                    },
+                },
+                EnemyTransition {
+                    from: Some(EnemyState::Cast),
+                    to: EnemyState::Chase,
+                    when: Default::default(),
+                },
+            ],
+        }
+    }
+}
+
+impl EnemyBehaviour {
+    pub fn next_state(&self, current: EnemyState, senses: &EnemySenses) -> Option<EnemyState> {
+        self.transitions
+            .iter()
+            .filter(|transition| transition.to != current)
+            .filter(|transition| transition.from.map(|from| from == current).unwrap_or(true))
+            .find(|transition| transition.when.is_met(senses))
+            .map(|transition| transition.to)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnemyAi {
+    pub behaviour: EnemyBehaviour,
+    pub state: EnemyState,
+    pub time_in_state: f32,
+    /// Heading angle used while wandering.
+    pub wander_angle: f32,
+    /// Side to circle around the player while strafing, either 1 or -1.
+    pub strafe_side: f32,
+}
+
+impl Default for EnemyAi {
+    fn default() -> Self {
+        Self::new(Default::default())
+    }
+}
+
+impl EnemyAi {
+    pub fn new(behaviour: EnemyBehaviour) -> Self {
+        Self {
+            state: behaviour.initial,
+            behaviour,
+            time_in_state: 0.0,
+            wander_angle: 0.0,
+            strafe_side: 1.0,
+        }
+    }
+
+    /// Advances state machine, returns true when state was changed.
+    pub fn think(&mut self, senses: &EnemySenses, delta_time: f32) -> bool {
+        self.time_in_state += delta_time;
+        let senses = EnemySenses {
+            time_in_state: self.time_in_state,
+            ..*senses
+        };
+        if let Some(state) = self.behaviour.next_state(self.state, &senses) {
+            self.state = state;
+            self.time_in_state = 0.0;
+            true
+        } else {
+            false
+        }
+    }
+}
@@ -0,0 +1,24 @@
+use micro_games_kit::third_party::serde::Deserialize;
+
+/// Local avoidance weights blended with the chase direction of an enemy.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(crate = "micro_games_kit::third_party::serde", default)]
+pub struct Flocking {
+    pub neighbour_range: f32,
+    pub chase: f32,
+    pub separation: f32,
+    pub alignment: f32,
+    pub cohesion: f32,
+}
+
+impl Default for Flocking {
+    fn default() -> Self {
+        Self {
+            neighbour_range: 100.0,
+            chase: 1.0,
+            separation: 2.0,
+            alignment: 0.3,
+            cohesion: 0.1,
+        }
+    }
+}
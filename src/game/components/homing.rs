@@ -0,0 +1,9 @@
+use hecs::Entity;
+
+/// Turns projectile towards its target.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Homing {
+    pub target: Entity,
+    /// Radians per second.
+    pub turn_rate: f32,
+}
@@ -0,0 +1,9 @@
+use crate::game::components::spell::Spell;
+
+/// Spell being spoken, it gets cast once wind-up time runs out.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Incantation {
+    pub text: String,
+    pub spell: Spell,
+    pub time_left: f32,
+}
@@ -0,0 +1,49 @@
+use super::{immobility::Immobility, velocity::Velocity};
+use hecs::{Entity, World};
+use micro_games_kit::third_party::vek::Vec2;
+
+/// Lets entity be pushed around by impulses, heavier ones get pushed less.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Knockback {
+    pub mass: f32,
+    /// Fraction of velocity lost every second, exponentially.
+    pub friction: f32,
+}
+
+impl Default for Knockback {
+    fn default() -> Self {
+        Self {
+            mass: 1.0,
+            friction: Self::FRICTION,
+        }
+    }
+}
+
+impl Knockback {
+    pub const FRICTION: f32 = 8.0;
+
+    pub fn new(mass: f32) -> Self {
+        Self {
+            mass,
+            ..Default::default()
+        }
+    }
+
+    /// Adds impulse to velocity of entity, immobile ones stay where they are.
+    /// Returns false when entity cannot be pushed.
+    pub fn push(world: &World, entity: Entity, impulse: Vec2<f32>) -> bool {
+        let Ok(mut query) =
+            world.query_one::<(&Knockback, &mut Velocity, Option<&Immobility>)>(entity)
+        else {
+            return false;
+        };
+        let Some((knockback, velocity, immobility)) = query.get() else {
+            return false;
+        };
+        if immobility.is_some_and(|immobility| immobility.time_left > 0.0) {
+            return false;
+        }
+        velocity.value += impulse / knockback.mass.max(f32::EPSILON);
+        true
+    }
+}
@@ -1,16 +1,33 @@
+pub mod affix;
 pub mod animation;
+pub mod boss;
+pub mod buff;
 pub mod collidable;
 pub mod damage;
 pub mod effect;
 pub mod enemy;
+pub mod enemy_ai;
+pub mod flocking;
 pub mod follow_player;
 pub mod health;
+pub mod homing;
 pub mod ignore_entity;
 pub mod immobility;
+pub mod incantation;
+pub mod knockback;
+pub mod obstacle;
+pub mod on_death;
 pub mod particle;
 pub mod particle_generator;
+pub mod pending_spell;
+pub mod pickup;
 pub mod player;
 pub mod projectile;
 pub mod speed;
 pub mod spell;
+pub mod spell_loadout;
 pub mod sprite_data;
+pub mod summon;
+pub mod velocity;
+pub mod wall;
+pub mod zone;
@@ -0,0 +1,17 @@
+use micro_games_kit::third_party::serde::Deserialize;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(
+    crate = "micro_games_kit::third_party::serde",
+    rename_all = "snake_case"
+)]
+pub enum ObstacleProjectileResponse {
+    #[default]
+    Stop,
+    Bounce,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Obstacle {
+    pub projectile_response: ObstacleProjectileResponse,
+}
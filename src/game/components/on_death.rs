@@ -0,0 +1,35 @@
+use crate::game::utils::magic::spell_tag::{SpellTagDamage, SpellTagSize};
+use micro_games_kit::third_party::serde::Deserialize;
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(
+    crate = "micro_games_kit::third_party::serde",
+    tag = "action",
+    rename_all = "snake_case"
+)]
+pub enum DeathBehaviour {
+    /// Spawns `count` enemies of given kind around the dead one, they keep its element.
+    Split { kind: String, count: usize },
+    /// Blast of dead one element hurting everything around.
+    Explode {
+        #[serde(default)]
+        size: SpellTagSize,
+        #[serde(default)]
+        damage: SpellTagDamage,
+    },
+    /// Puddle of dead one element, affects whoever steps in for `duration` seconds.
+    /// Enemies without element leave nothing.
+    Puddle {
+        #[serde(default)]
+        size: SpellTagSize,
+        duration: f32,
+    },
+    /// Health pickup dropped with given chance.
+    Loot { chance: f32, health: f32 },
+}
+
+/// Behaviours executed by `Death` system before entity gets despawned.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct OnDeath {
+    pub behaviours: Vec<DeathBehaviour>,
+}
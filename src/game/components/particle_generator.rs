@@ -1,3 +1,4 @@
+use micro_games_kit::third_party::vek::Vec2;
 use std::borrow::Cow;
 
 pub struct ParticleGenerator {
@@ -5,4 +6,6 @@ pub struct ParticleGenerator {
     pub emmission_accumulator: f32,
     pub emmission_time: f32,
     pub batch_size: usize,
+    // half of the segment along which particles are emitted.
+    pub spread: Vec2<f32>,
 }
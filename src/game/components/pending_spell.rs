@@ -0,0 +1,60 @@
+use crate::game::{
+    components::damage::DamageLayer, systems::player_controller::PlayerCastAction,
+    utils::magic::spell_tag::SpellTagTrigger,
+};
+use hecs::{Entity, World};
+
+/// Spell held back until its trigger condition is met, then cast.
+pub struct PendingSpell {
+    /// Cast to make, its spell has trigger cleared so it fires right away.
+    pub cast: PlayerCastAction,
+    pub caster: Entity,
+    pub trigger: SpellTagTrigger,
+    /// Layer of the pending spell, spells it casts get befriended when it is
+    /// on player layer.
+    pub layer: DamageLayer,
+    /// Whoever hit the caster, once spell waiting for a hit has got one.
+    pub attacker: Option<Entity>,
+    /// Seconds left until delayed spell fires or armed one fizzles out.
+    /// Delayed spells wait for as long as their duration word says.
+    pub ttl: f32,
+}
+
+impl PendingSpell {
+    /// Seconds traps and spells waiting for a hit stay armed.
+    pub const LIFETIME: f32 = 20.0;
+    /// Distance from trap at which stepping in sets it off.
+    pub const TRAP_RADIUS: f32 = 50.0;
+
+    /// Pending spell for cast with trigger, cast itself is given back when it has
+    /// none and should be cast right away.
+    pub fn new(mut cast: PlayerCastAction, caster: Entity) -> Result<Self, PlayerCastAction> {
+        let Some(trigger) = cast.spell.trigger.take() else {
+            return Err(cast);
+        };
+        let ttl = match trigger {
+            SpellTagTrigger::Delay => cast.spell.duration.time(),
+            SpellTagTrigger::Hit | SpellTagTrigger::Trap => Self::LIFETIME,
+        };
+        Ok(Self {
+            cast,
+            caster,
+            trigger,
+            layer: DamageLayer::None,
+            attacker: None,
+            ttl,
+        })
+    }
+
+    /// Arms spells of entity waiting for a hit against its attacker.
+    pub fn notify_hit(world: &World, entity: Entity, attacker: Entity) {
+        for (_, pending) in world.query::<&mut PendingSpell>().iter() {
+            if pending.trigger == SpellTagTrigger::Hit
+                && pending.caster == entity
+                && pending.attacker.is_none()
+            {
+                pending.attacker = Some(attacker);
+            }
+        }
+    }
+}
@@ -0,0 +1,6 @@
+/// Collected by player on touch, disappears after `time_left` seconds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Pickup {
+    pub health: f32,
+    pub time_left: f32,
+}
@@ -1,3 +1,4 @@
+use hecs::Entity;
 use micro_games_kit::third_party::vek::Vec2;
 
 pub struct Projectile {
@@ -6,6 +7,10 @@ pub struct Projectile {
     pub velocity: Vec2<f32>,
     pub alive_time: f32,
     pub ttl: f32,
+    /// Entity that cast spell of this projectile.
+    pub caster: Option<Entity>,
+    /// Entities already knocked back, each gets pushed only once.
+    pub pushed: Vec<Entity>,
 }
 
 impl Projectile {
@@ -16,6 +21,8 @@ impl Projectile {
             velocity: direction * speed,
             alive_time: 0.0,
             ttl,
+            caster: None,
+            pushed: Default::default(),
         }
     }
 }
@@ -1,6 +1,7 @@
 use crate::game::utils::magic::spell_tag::{
     SpellTagDamage, SpellTagDirection, SpellTagDuration, SpellTagEffect, SpellTagShape,
-    SpellTagSize, SpellTagSpeed, SpellTagTrajectory,
+    SpellTagSize, SpellTagSpeed, SpellTagSummon, SpellTagTarget, SpellTagTrajectory,
+    SpellTagTrigger,
 };
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -13,6 +14,11 @@ pub struct Spell {
     pub trajectory: SpellTagTrajectory,
     pub duration: SpellTagDuration,
     pub damage: SpellTagDamage,
+    pub target: SpellTagTarget,
+    /// Ally summoned instead of casting the spell shape.
+    pub summon: Option<SpellTagSummon>,
+    /// Condition to wait for before the spell is cast.
+    pub trigger: Option<SpellTagTrigger>,
 }
 
 impl Spell {
@@ -26,6 +32,9 @@ impl Spell {
             speed: SpellTagSpeed::Medium,
             trajectory: SpellTagTrajectory::Straight,
             damage: SpellTagDamage::Low,
+            target: SpellTagTarget::Caster,
+            summon: None,
+            trigger: None,
         }
     }
 }
@@ -0,0 +1,89 @@
+use crate::game::{
+    components::{effect::Effect, spell::Spell},
+    utils::magic::spell_tag::{
+        SpellTagDamage, SpellTagDirection, SpellTagDuration, SpellTagEffect, SpellTagShape,
+        SpellTagSize, SpellTagSpeed, SpellTagTrajectory,
+    },
+};
+use micro_games_kit::third_party::{
+    rand::{thread_rng, Rng},
+    serde::Deserialize,
+};
+
+/// Spell an enemy casts without speaking, like boss attack patterns, its element
+/// and duration come from enemy effect.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(crate = "micro_games_kit::third_party::serde")]
+pub struct EnemySpell {
+    #[serde(default)]
+    pub shape: SpellTagShape,
+    #[serde(default)]
+    pub size: SpellTagSize,
+    #[serde(default)]
+    pub speed: SpellTagSpeed,
+    #[serde(default)]
+    pub trajectory: SpellTagTrajectory,
+    #[serde(default)]
+    pub damage: SpellTagDamage,
+}
+
+impl EnemySpell {
+    pub fn to_spell(&self, effect: Option<&Effect>) -> Spell {
+        let effect = effect
+            .map(|effect| effect.to_effect_tag())
+            .unwrap_or(SpellTagEffect::None);
+        Spell {
+            direction: SpellTagDirection::Forward,
+            duration: if effect == SpellTagEffect::None {
+                SpellTagDuration::Instant
+            } else {
+                SpellTagDuration::Medium
+            },
+            effect,
+            shape: self.shape,
+            size: self.size,
+            speed: self.speed,
+            trajectory: self.trajectory,
+            damage: self.damage,
+            target: Default::default(),
+            summon: None,
+            trigger: None,
+        }
+    }
+}
+
+/// Words an enemy speaks when casting.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpellLoadout {
+    /// Words with their weights, enemy element word always leads.
+    pub words: Vec<(String, u32)>,
+    /// Range of number of words picked from `words`.
+    pub word_count: [usize; 2],
+    /// Seconds between casts, picked at random from this range.
+    pub cooldown: [f32; 2],
+    /// Seconds incantation is spoken before spell gets cast.
+    pub wind_up: f32,
+}
+
+impl Default for SpellLoadout {
+    fn default() -> Self {
+        Self {
+            words: vec![("small".to_owned(), 1)],
+            word_count: [1, 1],
+            cooldown: [5.0, 15.0],
+            wind_up: 1.0,
+        }
+    }
+}
+
+impl SpellLoadout {
+    pub fn next_word_count(&self) -> usize {
+        let [min, max] = self.word_count;
+        thread_rng().gen_range(min..=max.max(min))
+    }
+
+    pub fn next_cooldown(&self) -> f32 {
+        let [min, max] = self.cooldown;
+        thread_rng().gen_range(min..=max.max(min))
+    }
+}
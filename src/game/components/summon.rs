@@ -0,0 +1,23 @@
+use crate::game::utils::magic::spell_tag::{SpellTagEffect, SpellTagSummon};
+use hecs::Entity;
+
+/// Ally fighting for its owner until its time runs out.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Summon {
+    pub kind: SpellTagSummon,
+    pub owner: Entity,
+    /// Element of spells it casts.
+    pub effect: SpellTagEffect,
+    /// Seconds left until next spell.
+    pub cooldown: f32,
+    /// Seconds since it was summoned, tells which summons are the oldest.
+    pub alive_time: f32,
+    pub ttl: f32,
+}
+
+impl Summon {
+    /// Most summons one owner can have at once, oldest ones go away first.
+    pub const LIMIT: usize = 3;
+    /// Summons last this many times longer than duration of spells.
+    pub const LIFETIME_MULTIPLIER: f32 = 5.0;
+}
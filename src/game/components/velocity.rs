@@ -0,0 +1,8 @@
+use micro_games_kit::third_party::vek::Vec2;
+
+/// Movement given by impulses rather than by entity itself, slowed down by
+/// friction of its `Knockback`.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Velocity {
+    pub value: Vec2<f32>,
+}
@@ -0,0 +1,75 @@
+use crate::game::{
+    components::{
+        damage::{Damage, DamageLayer},
+        health::Health,
+        projectile::Projectile,
+    },
+    utils::magic::spell_tag::SpellTagEffect,
+};
+use hecs::{Entity, World};
+
+/// Solid barrier raised by wall spells. Blocks movement and projectiles of
+/// everyone but its caster and its side, and affects whatever touches it
+/// every `interval` seconds with its element.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Wall {
+    pub effect: SpellTagEffect,
+    pub caster: Entity,
+    /// Side the wall belongs to, taken from health layer of its caster.
+    pub layer: DamageLayer,
+    pub interval: f32,
+    pub time_accumulator: f32,
+    pub lifetime: f32,
+    pub ttl: f32,
+}
+
+impl Wall {
+    pub const INTERVAL: f32 = 0.5;
+    /// Distance in front of the caster at which wall is raised.
+    pub const DISTANCE: f32 = 100.0;
+    pub const THICKNESS: f32 = 15.0;
+    /// Particles emitted by undamaged wall, cracked walls emit less of them.
+    pub const PARTICLES: usize = 80;
+    /// Seconds water walls freeze things touching them for.
+    pub const FREEZE_TIME: f32 = 1.0;
+    /// Damage electric walls zap things touching them with.
+    pub const ZAP_DAMAGE: f32 = 5.0;
+
+    pub fn new(effect: SpellTagEffect, caster: Entity, lifetime: f32) -> Self {
+        Self {
+            effect,
+            caster,
+            layer: DamageLayer::None,
+            interval: Self::INTERVAL,
+            time_accumulator: 0.0,
+            lifetime,
+            ttl: lifetime,
+        }
+    }
+
+    /// Tells if entity can pass through the wall: its caster, projectiles of
+    /// its caster and anything of the same side as the wall, judged by damage
+    /// layer of spells and health layer of bodies.
+    pub fn passes(world: &World, wall_entity: Entity, entity: Entity) -> bool {
+        let Ok(wall) = world.get::<&Wall>(wall_entity).map(|wall| *wall) else {
+            return false;
+        };
+        let caster = world
+            .get::<&Projectile>(entity)
+            .ok()
+            .and_then(|projectile| projectile.caster)
+            .unwrap_or(entity);
+        if caster == wall.caster {
+            return true;
+        }
+        wall.layer != DamageLayer::None && Self::layer(world, entity) == Some(wall.layer)
+    }
+
+    fn layer(world: &World, entity: Entity) -> Option<DamageLayer> {
+        world
+            .get::<&Damage>(entity)
+            .map(|damage| damage.layer)
+            .or_else(|_| world.get::<&Health>(entity).map(|health| health.layer))
+            .ok()
+    }
+}
@@ -0,0 +1,162 @@
+use crate::game::{
+    components::{
+        damage::DamageLayer,
+        effect::{Effect, EffectReaction},
+        spell::Spell,
+    },
+    utils::magic::spell_tag::SpellTagEffect,
+};
+use hecs::Entity;
+use micro_games_kit::third_party::vek::Vec2;
+
+/// Stationary area that affects everything standing in it every `interval`
+/// seconds, instead of colliding like projectiles do.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Zone {
+    pub effect: SpellTagEffect,
+    /// Set for zones made out of two reacting ones.
+    pub reaction: EffectReaction,
+    pub radius: f32,
+    /// Damage dealt on every tick.
+    pub damage: f32,
+    pub layer: DamageLayer,
+    pub interval: f32,
+    pub time_accumulator: f32,
+    pub lifetime: f32,
+    pub ttl: f32,
+    /// Entity not affected by the zone, so casters can stand in their own zones.
+    pub caster: Option<Entity>,
+}
+
+impl Zone {
+    pub const INTERVAL: f32 = 0.5;
+
+    pub fn new(effect: SpellTagEffect, radius: f32, damage: f32, lifetime: f32) -> Self {
+        Self {
+            effect,
+            reaction: EffectReaction::None,
+            radius,
+            damage,
+            layer: DamageLayer::None,
+            interval: Self::INTERVAL,
+            // first tick happens right away.
+            time_accumulator: Self::INTERVAL,
+            lifetime,
+            ttl: lifetime,
+            caster: None,
+        }
+    }
+
+    /// Zone of given spell on the side of its caster.
+    pub fn from_spell(spell: &Spell, caster: Entity, layer: DamageLayer) -> Self {
+        Self {
+            layer,
+            caster: Some(caster),
+            ..Self::new(
+                spell.effect,
+                spell.size.radius(),
+                spell.damage.damage(),
+                spell.duration.time(),
+            )
+        }
+    }
+
+    pub fn overlaps(&self, position: Vec2<f32>, other: &Self, other_position: Vec2<f32>) -> bool {
+        position.distance(other_position) < self.radius + other.radius
+    }
+
+    /// Zone made out of two zones whose elements react, for example fire and
+    /// water turn into steam cloud. `None` when elements do not react. Result
+    /// keeps layer and caster the zones share, mixed ones hurt everyone.
+    pub fn combine(&self, other: &Self) -> Option<Self> {
+        let reaction = Effect::from(self.effect).react(&mut Effect::from(other.effect));
+        let radius = self.radius.max(other.radius);
+        let lifetime = self.ttl.max(other.ttl);
+        let damage = self.damage + other.damage;
+        let result = match reaction {
+            EffectReaction::None => return None,
+            EffectReaction::Steam => {
+                Self::new(SpellTagEffect::None, radius * 1.5, damage, lifetime)
+            }
+            EffectReaction::Paralize => Self::new(SpellTagEffect::None, radius, 0.0, lifetime),
+            // explodes at once and is gone.
+            EffectReaction::Explode => Self::new(
+                SpellTagEffect::None,
+                radius * 2.0,
+                damage + reaction.damage(),
+                Self::INTERVAL * 0.5,
+            ),
+        };
+        let layer = if self.layer == other.layer {
+            self.layer
+        } else {
+            DamageLayer::None
+        };
+        let caster = if self.caster == other.caster {
+            self.caster
+        } else {
+            None
+        };
+        Some(Self {
+            reaction,
+            layer,
+            caster,
+            ..result
+        })
+    }
+
+    pub fn texture(&self) -> &'static str {
+        match (self.reaction, self.effect) {
+            (EffectReaction::Steam, _) => "zone/steam",
+            (EffectReaction::Paralize, _) => "zone/paralized",
+            (EffectReaction::Explode, _) => "zone/explosion",
+            (EffectReaction::None, SpellTagEffect::Fire) => "zone/fire",
+            (EffectReaction::None, SpellTagEffect::Water) => "zone/water",
+            (EffectReaction::None, SpellTagEffect::Electric) => "zone/electric",
+            (EffectReaction::None, SpellTagEffect::None) => "zone/none",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zones_combine() {
+        let fire = Zone::new(SpellTagEffect::Fire, 40.0, 5.0, 2.0);
+        let water = Zone::new(SpellTagEffect::Water, 80.0, 1.0, 8.0);
+        let electric = Zone::new(SpellTagEffect::Electric, 20.0, 1.0, 0.5);
+
+        assert!(fire.overlaps(Vec2::zero(), &water, Vec2::new(100.0, 0.0)));
+        assert!(!fire.overlaps(Vec2::zero(), &electric, Vec2::new(100.0, 0.0)));
+        assert_eq!(fire.combine(&fire), None);
+
+        let steam = fire.combine(&water).unwrap();
+        assert_eq!(steam.reaction, EffectReaction::Steam);
+        assert_eq!(steam.effect, SpellTagEffect::None);
+        assert_eq!(steam.radius, 120.0);
+        assert_eq!(steam.damage, 6.0);
+        assert_eq!(steam.ttl, 8.0);
+        assert_eq!(steam.texture(), "zone/steam");
+
+        let paralized = electric.combine(&water).unwrap();
+        assert_eq!(paralized.reaction, EffectReaction::Paralize);
+        assert_eq!(paralized.damage, 0.0);
+
+        let explosion = electric.combine(&fire).unwrap();
+        assert_eq!(explosion.reaction, EffectReaction::Explode);
+        assert!(explosion.ttl < Zone::INTERVAL);
+        assert_eq!(explosion.damage, 56.0);
+        assert_eq!(explosion.layer, DamageLayer::None);
+
+        let hostile = |zone: &Zone| Zone {
+            layer: DamageLayer::Enemy,
+            ..zone.clone()
+        };
+        let explosion = hostile(&electric).combine(&hostile(&fire)).unwrap();
+        assert_eq!(explosion.layer, DamageLayer::Enemy);
+        let steam = hostile(&fire).combine(&water).unwrap();
+        assert_eq!(steam.layer, DamageLayer::None);
+    }
+}
@@ -1,4 +1,4 @@
-use crate::game::ui::make_theme;
+use crate::game::{ui::make_theme, utils::audio::Audio};
 use micro_games_kit::{
     context::GameContext,
     game::{GameState, GameStateChange},
@@ -14,7 +14,7 @@ use micro_games_kit::{
     },
 };
 
-use super::new_gameplay::NewGameplay;
+use super::new_gameplay::{GameMode, NewGameplay};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum GameEndReason {
@@ -22,25 +22,31 @@ pub enum GameEndReason {
     Won,
 }
 
-impl ToString for GameEndReason {
-    fn to_string(&self) -> String {
+impl std::fmt::Display for GameEndReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Self::Lost => "YOU LOST".to_owned(),
-            Self::Won => "YOU WON".to_owned(),
+            Self::Lost => write!(f, "YOU LOST"),
+            Self::Won => write!(f, "YOU WON"),
         }
     }
 }
 
 pub struct GameEnd {
+    reason: GameEndReason,
+    mode: GameMode,
     time: f32,
+    audio: Audio,
     restart: InputActionRef,
     exit: InputActionRef,
 }
 
 impl GameEnd {
-    pub fn new(time: f32) -> Self {
+    pub fn new(reason: GameEndReason, mode: GameMode, time: f32, audio: Audio) -> Self {
         Self {
+            reason,
+            mode,
             time,
+            audio,
             restart: Default::default(),
             exit: Default::default(),
         }
@@ -71,7 +77,10 @@ impl GameState for GameEnd {
 
     fn fixed_update(&mut self, context: GameContext, _delta_time: f32) {
         if self.restart.get().is_pressed() {
-            *context.state_change = GameStateChange::Swap(Box::<NewGameplay>::default());
+            *context.state_change = GameStateChange::Swap(Box::new(NewGameplay::new(
+                std::mem::take(&mut self.audio),
+                self.mode,
+            )));
         }
         if self.exit.get().is_pressed() {
             *context.state_change = GameStateChange::Pop;
@@ -82,22 +91,29 @@ impl GameState for GameEnd {
         context.graphics.color = [1.0, 1.0, 1.0, 1.0];
 
         apply_shared_props(make_theme(), || {
-            image_box(ImageBoxProps {
-                content_keep_aspect_ratio: Some(ImageBoxAspectRatio {
-                    horizontal_alignment: 0.5,
-                    vertical_alignment: 0.5,
-                    outside: false,
-                }),
-                material: ImageBoxMaterial::Image(ImageBoxImage {
-                    id: "ui/lost".to_owned(),
+            if self.reason == GameEndReason::Lost {
+                image_box(ImageBoxProps {
+                    content_keep_aspect_ratio: Some(ImageBoxAspectRatio {
+                        horizontal_alignment: 0.5,
+                        vertical_alignment: 0.5,
+                        outside: false,
+                    }),
+                    material: ImageBoxMaterial::Image(ImageBoxImage {
+                        id: "ui/lost".to_owned(),
+                        ..Default::default()
+                    }),
                     ..Default::default()
-                }),
-                ..Default::default()
-            });
+                });
+            }
 
             nav_vertical_box((), || {
                 text_box(TextBoxProps {
-                    text: format!("Survived: {:.2}", self.time),
+                    text: match self.reason {
+                        GameEndReason::Lost => format!("Survived: {:.2}", self.time),
+                        GameEndReason::Won => {
+                            format!("{} in {:.2} seconds", self.reason, self.time)
+                        }
+                    },
                     horizontal_align: TextBoxHorizontalAlign::Center,
                     vertical_align: TextBoxVerticalAlign::Top,
                     font: TextBoxFont {
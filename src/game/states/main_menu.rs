@@ -0,0 +1,119 @@
+use super::new_gameplay::{GameMode, NewGameplay};
+use crate::game::{
+    ui::{make_theme, text_button::text_button},
+    utils::audio::Audio,
+};
+use micro_games_kit::{
+    context::GameContext,
+    game::{GameState, GameStateChange},
+    third_party::{
+        raui_core::layout::CoordsMappingScaling,
+        raui_immediate::apply_shared_props,
+        raui_immediate_widgets::core::{
+            containers::{content_box, nav_vertical_box, VerticalBoxProps},
+            text_box, Color, ContentBoxItemLayout, FlexBoxItemLayout, Rect, TextBoxFont,
+            TextBoxHorizontalAlign, TextBoxProps, TextBoxVerticalAlign, Vec2,
+        },
+    },
+};
+
+pub struct MainMenu {
+    audio: Audio,
+    selected: Option<GameMode>,
+}
+
+impl MainMenu {
+    pub fn new(audio: Audio) -> Self {
+        Self {
+            audio,
+            selected: None,
+        }
+    }
+}
+
+impl GameState for MainMenu {
+    fn enter(&mut self, context: GameContext) {
+        context.graphics.color = [0.0, 0.3, 0.0, 1.0];
+        context.gui.coords_map_scaling = CoordsMappingScaling::FitVertical(1024.0);
+    }
+
+    fn fixed_update(&mut self, context: GameContext, _delta_time: f32) {
+        if let Some(mode) = self.selected.take() {
+            *context.state_change = GameStateChange::Swap(Box::new(NewGameplay::new(
+                std::mem::take(&mut self.audio),
+                mode,
+            )));
+        }
+    }
+
+    fn draw_gui(&mut self, _context: GameContext) {
+        let button_layout = FlexBoxItemLayout {
+            basis: Some(100.0),
+            grow: 0.0,
+            shrink: 0.0,
+            ..Default::default()
+        };
+
+        apply_shared_props(make_theme(), || {
+            content_box(
+                ContentBoxItemLayout {
+                    anchors: Rect {
+                        left: 0.5,
+                        right: 0.5,
+                        top: 0.5,
+                        bottom: 0.5,
+                    },
+                    margin: Rect {
+                        left: -250.0,
+                        right: -250.0,
+                        top: -250.0,
+                        bottom: -250.0,
+                    },
+                    align: Vec2 { x: 0.5, y: 0.5 },
+                    ..Default::default()
+                },
+                || {
+                    nav_vertical_box(
+                        VerticalBoxProps {
+                            separation: 30.0,
+                            ..Default::default()
+                        },
+                        || {
+                            text_box((
+                                button_layout.clone(),
+                                TextBoxProps {
+                                    text: "RED HOOD".to_owned(),
+                                    horizontal_align: TextBoxHorizontalAlign::Center,
+                                    vertical_align: TextBoxVerticalAlign::Middle,
+                                    font: TextBoxFont {
+                                        name: "roboto".to_owned(),
+                                        size: 80.0,
+                                    },
+                                    color: Color {
+                                        r: 0.9,
+                                        g: 0.1,
+                                        b: 0.1,
+                                        a: 1.0,
+                                    },
+                                    ..Default::default()
+                                },
+                            ));
+
+                            if text_button(button_layout.clone(), "SURVIVAL", &mut self.audio)
+                                .trigger_stop()
+                            {
+                                self.selected = Some(GameMode::Survival);
+                            }
+
+                            if text_button(button_layout.clone(), "CAMPAIGN", &mut self.audio)
+                                .trigger_stop()
+                            {
+                                self.selected = Some(GameMode::Campaign);
+                            }
+                        },
+                    );
+                },
+            );
+        });
+    }
+}
@@ -1,3 +1,4 @@
 pub mod game_end;
+pub mod main_menu;
 pub mod new_gameplay;
 pub mod preloader;
@@ -1,40 +1,68 @@
-use super::game_end::GameEnd;
+use super::game_end::{GameEnd, GameEndReason};
 use crate::game::{
     components::{
+        affix::Elite,
         animation::Animation,
+        boss::Boss,
+        buff::{Buff, Buffs},
         collidable::Collidable,
         damage::{Damage, DamageLayer},
         effect::Effect,
         health::Health,
         ignore_entity::IgnoreEntity,
         immobility::Immobility,
+        incantation::Incantation,
+        knockback::Knockback,
+        obstacle::{Obstacle, ObstacleProjectileResponse},
         particle_generator::ParticleGenerator,
+        pending_spell::PendingSpell,
         sprite_data::SpriteData,
+        summon::Summon,
+        velocity::Velocity,
+        wall::Wall,
+        zone::Zone,
     },
     systems::{
-        animation_controller::AnimationController, collision_detector::CollisionDetector,
+        animation_controller::AnimationController, boss_controller::BossController,
+        buff_controller::BuffController, collision_detector::CollisionDetector,
         damage_dealer::DamageDealer, death::Death, effects_reactions::EffectsReactions,
         enemy_controller::EnemyController, enemy_jump_animation::EnemyJumpAnimation,
-        enemy_spawn::EnemySpawn, immobility_controller::ImmobilityController,
-        particle_manager::ParticleManager, player_controller::PlayerCastAction,
-        slime_color::SlimeColor, spell_controller::SpellController,
+        enemy_spawn::EnemySpawn, homing_controller::HomingController,
+        immobility_controller::ImmobilityController, movement::Movement,
+        navigation_controller::NavigationController, obstacle_collision::ObstacleCollision,
+        particle_manager::ParticleManager, pending_spell_controller::PendingSpellController,
+        pickup_collector::PickupCollector, player_controller::PlayerCastAction,
+        slime_color::SlimeColor, spell_clash::SpellClash, spell_controller::SpellController,
+        summon_controller::SummonController, wall_controller::WallController,
+        wave_director::WaveDirector, zone_controller::ZoneController, zone_renderer::ZoneRenderer,
+    },
+    ui::{
+        buff_icons::buff_icons, health_bar::health_bar, word_picker::word_picker,
+        world_to_screen_content_layout,
     },
-    ui::{health_bar::health_bar, world_to_screen_content_layout},
     utils::{
         audio::Audio,
         magic::spell_tag::{
             SpellTag, SpellTagDamage, SpellTagDirection, SpellTagDuration, SpellTagEffect,
-            SpellTagShape, SpellTagSize, SpellTagSpeed, SpellTagTrajectory,
+            SpellTagShape, SpellTagSize, SpellTagSpeed, SpellTagSummon, SpellTagTarget,
+            SpellTagTrajectory, SpellTagTrigger,
         },
     },
 };
 use crate::game::{
     components::{player::Player, projectile::Projectile},
     systems::{
-        player_controller::PlayerController, projectile_controller::ProjectileController,
+        player_controller::{PlayerBindings, PlayerController},
+        projectile_controller::ProjectileController,
         sprite_renderer::SpriteRenderer,
     },
-    utils::{magic::database::WordToSpellTagDatabase, space::SpaceObject},
+    utils::{
+        enemies::EnemyDatabase,
+        magic::{database::WordToSpellTagDatabase, spellbook::Spellbook},
+        map::MapData,
+        space::{Space, SpaceCollider, SpaceObject},
+        waves::WaveScript,
+    },
 };
 use hecs::{Entity, World};
 use micro_games_kit::{
@@ -54,20 +82,35 @@ use micro_games_kit::{
         },
         spitfire_glow::{graphics::CameraScaling, renderer::GlowTextureFiltering},
         spitfire_input::{InputActionRef, InputConsume, InputMapping, VirtualAction},
-        vek::{Transform, Vec2},
+        vek::{Transform, Vec2, Vec3},
         windowing::event::VirtualKeyCode,
     },
 };
 
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum GameMode {
+    /// Waves keep coming until player dies.
+    #[default]
+    Survival,
+    /// Defeating a boss wins the game.
+    Campaign,
+}
+
 pub struct NewGameplay {
+    mode: GameMode,
     map: [Sprite; 4],
     exit: InputActionRef,
-    music: StaticSoundHandle,
+    music: Option<StaticSoundHandle>,
+    audio: Audio,
     world: World,
+    space: Space,
     player_controller: PlayerController,
-    enemy_spawn: EnemySpawn,
+    wave_director: WaveDirector,
+    enemy_database: EnemyDatabase,
     particle_manager: ParticleManager,
+    navigation_controller: NavigationController,
     word_to_spell_tag_database: WordToSpellTagDatabase,
+    spellbook: Spellbook,
     alive_time_seconds: f32,
     tip_time_seconds: f32,
     tip_content: String,
@@ -76,6 +119,7 @@ pub struct NewGameplay {
 impl Default for NewGameplay {
     fn default() -> Self {
         Self {
+            mode: Default::default(),
             map: [
                 Sprite::single(SpriteTexture {
                     sampler: "u_image".into(),
@@ -103,15 +147,15 @@ impl Default for NewGameplay {
                 .pivot((0.0, 0.0).into()),
             ],
             exit: Default::default(),
-            music: Audio::write()
-                .write()
-                .unwrap()
-                .play("music/ambient")
-                .unwrap(),
+            music: None,
+            audio: Default::default(),
             world: World::new(),
+            space: Default::default(),
             player_controller: PlayerController::default(),
-            enemy_spawn: EnemySpawn::new(1000.0, 3.0, 30),
+            wave_director: WaveDirector::new(WaveScript::load(), EnemySpawn::new(1000.0, 3.0, 30)),
+            enemy_database: EnemyDatabase::load(),
             particle_manager: ParticleManager {},
+            navigation_controller: Default::default(),
             word_to_spell_tag_database: WordToSpellTagDatabase::default()
                 // Fire
                 .with("fire", SpellTag::Effect(SpellTagEffect::Fire))
@@ -306,11 +350,33 @@ impl Default for NewGameplay {
                 // Down
                 .with("down", SpellTag::Direction(SpellTagDirection::Down))
                 .with("stop", SpellTag::Direction(SpellTagDirection::Down))
-                .with("me", SpellTag::Direction(SpellTagDirection::Down))
-                .with("self", SpellTag::Direction(SpellTagDirection::Down))
                 .with("bomb", SpellTag::Direction(SpellTagDirection::Down))
                 .with("under", SpellTag::Direction(SpellTagDirection::Down))
                 .with("freeze", SpellTag::Direction(SpellTagDirection::Down))
+                // Target - Cursor
+                .with("there", SpellTag::Target(SpellTagTarget::Cursor))
+                .with("at", SpellTag::Target(SpellTagTarget::Cursor))
+                .with("upon", SpellTag::Target(SpellTagTarget::Cursor))
+                .with("yonder", SpellTag::Target(SpellTagTarget::Cursor))
+                .with("remote", SpellTag::Target(SpellTagTarget::Cursor))
+                // Summon
+                .with("golem", SpellTag::Summon(SpellTagSummon::Golem))
+                .with("familiar", SpellTag::Summon(SpellTagSummon::Familiar))
+                .with("spirit", SpellTag::Summon(SpellTagSummon::Spirit))
+                .with("minion", SpellTag::Summon(SpellTagSummon::Minion))
+                // Trigger
+                .with("later", SpellTag::Trigger(SpellTagTrigger::Delay))
+                .with("delay", SpellTag::Trigger(SpellTagTrigger::Delay))
+                .with("wait", SpellTag::Trigger(SpellTagTrigger::Delay))
+                .with("when", SpellTag::Trigger(SpellTagTrigger::Hit))
+                .with("hit", SpellTag::Trigger(SpellTagTrigger::Hit))
+                .with("revenge", SpellTag::Trigger(SpellTagTrigger::Hit))
+                .with("trap", SpellTag::Trigger(SpellTagTrigger::Trap))
+                .with("mine", SpellTag::Trigger(SpellTagTrigger::Trap))
+                // Target - Oneself
+                .with("me", SpellTag::Target(SpellTagTarget::Oneself))
+                .with("self", SpellTag::Target(SpellTagTarget::Oneself))
+                .with("myself", SpellTag::Target(SpellTagTarget::Oneself))
                 // Shape - Point
                 .with("ball", SpellTag::Shape(SpellTagShape::Point))
                 .with("sphere", SpellTag::Shape(SpellTagShape::Point))
@@ -407,6 +473,7 @@ impl Default for NewGameplay {
                 .with("emulsion", SpellTag::Damage(SpellTagDamage::High))
                 .with("moisture", SpellTag::Damage(SpellTagDamage::High))
                 .with("liquor", SpellTag::Damage(SpellTagDamage::High)),
+            spellbook: Default::default(),
             alive_time_seconds: 0.0,
             tip_time_seconds: 0.0,
             tip_content: Default::default(),
@@ -416,6 +483,8 @@ impl Default for NewGameplay {
 
 impl GameState for NewGameplay {
     fn enter(&mut self, context: GameContext) {
+        self.music = self.audio.play("music/ambient");
+
         context.graphics.color = [0.0, 0.3, 0.0, 1.0];
         context.graphics.main_camera.screen_alignment = 0.5.into();
         context.graphics.main_camera.scaling = CameraScaling::FitVertical(800.0);
@@ -429,6 +498,13 @@ impl GameState for NewGameplay {
             ));
 
         self.player_controller.init(context.input);
+        self.spellbook = Spellbook::starter(&self.word_to_spell_tag_database);
+
+        let map = MapData::load();
+        map.spawn_obstacles(&mut self.world);
+        self.navigation_controller = NavigationController::new(map.navigation);
+        self.wave_director.enemy_spawn.arena_half_extent = self.player_controller.walk_area;
+        self.wave_director.enemy_spawn.zones = map.spawn_zones;
 
         self.world.spawn((
             Player {
@@ -441,13 +517,13 @@ impl GameState for NewGameplay {
                 space_object: Some(SpaceObject {
                     entity: None,
                     position: Vec2::default(),
-                    collider_radius: 40.0,
+                    collider: SpaceCollider::circle(40.0),
                 }),
             },
             Health {
                 value: 150.0,
                 limit: 150.0,
-                layer: DamageLayer::None,
+                layer: DamageLayer::Player,
             },
             Effect {
                 electricity: false,
@@ -459,13 +535,17 @@ impl GameState for NewGameplay {
                 ..Default::default()
             },
             Immobility { time_left: 0.0 },
+            Velocity::default(),
+            Knockback::default(),
         ));
     }
 
     fn exit(&mut self, context: GameContext) {
         context.input.pop_mapping();
 
-        let _ = self.music.stop(Default::default());
+        if let Some(music) = self.music.as_mut() {
+            let _ = music.stop(Default::default());
+        }
     }
 
     fn fixed_update(&mut self, mut context: GameContext, delta_time: f32) {
@@ -478,33 +558,86 @@ impl GameState for NewGameplay {
         if self.tip_time_seconds <= 0.0 {
             self.tip_time_seconds = 2.0;
             self.tip_content = self.word_to_spell_tag_database.random_word().to_uppercase();
+            self.spellbook
+                .discover(&self.word_to_spell_tag_database, &self.tip_content);
+        }
+        // words heard from enemies can be used by the player too.
+        for (_, incantation) in self.world.query::<&Incantation>().iter() {
+            self.spellbook
+                .discover(&self.word_to_spell_tag_database, &incantation.text);
         }
 
-        self.enemy_spawn.run(&mut self.world, delta_time);
+        self.wave_director.run(
+            &mut self.world,
+            &self.space,
+            &self.enemy_database,
+            self.alive_time_seconds,
+            delta_time,
+        );
         self.player_controller.run(
             &mut self.world,
+            &mut self.audio,
             &mut context,
             delta_time,
             &self.word_to_spell_tag_database,
+            &mut self.spellbook,
+        );
+        self.navigation_controller.run(&self.world, delta_time);
+        EnemyController::run(
+            &mut self.world,
+            &self.space,
+            &mut self.audio,
+            delta_time,
+            &self.word_to_spell_tag_database,
+            &self.navigation_controller,
+            &self.wave_director.enemy_spawn,
         );
-        EnemyController::run(&mut self.world, delta_time);
+        BossController::run(&mut self.world, &mut self.audio, delta_time);
+        SummonController::run(&mut self.world, &self.space, &mut self.audio, delta_time);
+        Movement::run(&self.world, delta_time);
+        ObstacleCollision::run(&self.world, &self.space);
         AnimationController::run(&self.world, delta_time);
-        ProjectileController::run(&mut self.world, delta_time);
-        CollisionDetector::run(&self.world);
-        EffectsReactions::run(&mut self.world);
+        HomingController::run(&self.world, delta_time);
+        ProjectileController::run(&mut self.world, &self.space, delta_time);
+        CollisionDetector::run(&self.world, &mut self.space);
+        SpellClash::run(&mut self.world, &self.space, &mut self.audio);
+        EffectsReactions::run(&mut self.world, &self.space);
         SpellController::run(&mut self.world);
-        DamageDealer::run(&self.world);
+        DamageDealer::run(&self.world, &self.space);
+        ZoneController::run(&mut self.world, &self.space, delta_time);
+        WallController::run(&mut self.world, &self.space, delta_time);
+        PendingSpellController::run(&mut self.world, &mut self.audio, delta_time);
+        PickupCollector::run(&mut self.world, &self.space, delta_time);
         self.particle_manager.process(&mut self.world, delta_time);
         SlimeColor::run(&self.world);
         EnemyJumpAnimation::run(&self.world, delta_time);
         ImmobilityController::run(&self.world, delta_time);
+        BuffController::run(&mut self.world, delta_time);
+
+        let boss_defeated = self
+            .world
+            .query::<&Health>()
+            .with::<&Boss>()
+            .iter()
+            .any(|(_, health)| health.value <= 0.0);
 
         // always keep death last in the frame to run!
-        Death::run(&mut self.world);
+        Death::run(&mut self.world, &mut self.audio, &self.enemy_database);
 
-        if self.world.query::<&Player>().iter().next().is_none() {
-            *context.state_change =
-                GameStateChange::Swap(Box::new(GameEnd::new(self.alive_time_seconds)));
+        let reason = if self.world.query::<&Player>().iter().next().is_none() {
+            Some(GameEndReason::Lost)
+        } else if self.mode == GameMode::Campaign && boss_defeated {
+            Some(GameEndReason::Won)
+        } else {
+            None
+        };
+        if let Some(reason) = reason {
+            *context.state_change = GameStateChange::Swap(Box::new(GameEnd::new(
+                reason,
+                self.mode,
+                self.alive_time_seconds,
+                std::mem::take(&mut self.audio),
+            )));
         }
     }
 
@@ -513,6 +646,7 @@ impl GameState for NewGameplay {
             sprite.draw(context.draw, context.graphics);
         }
 
+        ZoneRenderer::run(&self.world, &mut context);
         SpriteRenderer::run(&self.world, &mut context);
         self.particle_manager.draw(&self.world, &mut context);
     }
@@ -528,9 +662,9 @@ impl GameState for NewGameplay {
         };
 
         {
-            for (_, (transform, health)) in self
+            for (_, (transform, health, elite)) in self
                 .world
-                .query::<(&Transform<f32, f32, f32>, &Health)>()
+                .query::<(&Transform<f32, f32, f32>, &Health, Option<&Elite>)>()
                 .iter()
             {
                 let layout = world_to_screen_content_layout(
@@ -540,6 +674,102 @@ impl GameState for NewGameplay {
                 );
 
                 health_bar(layout, health.value, health.limit);
+
+                if let Some(elite) = elite {
+                    let layout = world_to_screen_content_layout(
+                        transform.position.xy(),
+                        Rect {
+                            left: -150.0,
+                            right: 150.0,
+                            top: -50.0,
+                            bottom: -25.0,
+                        },
+                        &context,
+                    );
+
+                    text_box((
+                        layout,
+                        TextBoxProps {
+                            text: elite.title().to_uppercase(),
+                            horizontal_align: TextBoxHorizontalAlign::Center,
+                            vertical_align: TextBoxVerticalAlign::Middle,
+                            font: TextBoxFont {
+                                name: "roboto".to_owned(),
+                                size: 20.0,
+                            },
+                            color: Color {
+                                r: 1.0,
+                                g: 0.8,
+                                b: 0.1,
+                                a: 1.0,
+                            },
+                            ..Default::default()
+                        },
+                    ));
+                }
+            }
+
+            for (_, (transform, incantation)) in self
+                .world
+                .query::<(&Transform<f32, f32, f32>, &Incantation)>()
+                .iter()
+            {
+                let layout = world_to_screen_content_layout(
+                    transform.position.xy(),
+                    Rect {
+                        left: -150.0,
+                        right: 150.0,
+                        top: -140.0,
+                        bottom: -80.0,
+                    },
+                    &context,
+                );
+
+                content_box(layout, || {
+                    image_box(ImageBoxProps {
+                        material: ImageBoxMaterial::Image(ImageBoxImage {
+                            id: "ui/panel".to_owned(),
+                            scaling: ImageBoxImageScaling::Frame(ImageBoxFrame {
+                                source: 0.5.into(),
+                                destination: 20.0.into(),
+                                frame_only: false,
+                                frame_keep_aspect_ratio: false,
+                            }),
+                            ..Default::default()
+                        }),
+                        ..Default::default()
+                    });
+
+                    text_box(TextBoxProps {
+                        text: incantation.text.to_uppercase(),
+                        horizontal_align: TextBoxHorizontalAlign::Center,
+                        vertical_align: TextBoxVerticalAlign::Middle,
+                        font: TextBoxFont {
+                            name: "roboto".to_owned(),
+                            size: 28.0,
+                        },
+                        color: Color {
+                            r: 0.1,
+                            g: 0.1,
+                            b: 0.6,
+                            a: 1.0,
+                        },
+                        ..Default::default()
+                    });
+                });
+            }
+        }
+
+        for (_, buffs) in self.world.query::<&Buffs>().with::<&Player>().iter() {
+            buff_icons(&buffs.active);
+        }
+
+        let picker = &self.player_controller.picker;
+        if picker.open {
+            let groups = self.spellbook.groups(&self.word_to_spell_tag_database);
+            match groups.get(picker.group) {
+                Some((category, words)) => word_picker(category.name(), words, picker.selected),
+                None => word_picker("No words known", &[], None),
             }
         }
 
@@ -576,7 +806,14 @@ impl GameState for NewGameplay {
                 });
 
                 text_box(TextBoxProps {
-                    text: if self.player_controller.spell_text.is_empty() {
+                    text: if !self.player_controller.accepts_typing() {
+                        format!(
+                            "Moving... {} to incant",
+                            PlayerBindings::action_name(
+                                self.player_controller.bindings.toggle_incant
+                            )
+                        )
+                    } else if self.player_controller.spell_text.is_empty() {
                         "Type your spell...".to_owned()
                     } else {
                         self.player_controller.spell_text.to_uppercase()
@@ -624,7 +861,12 @@ impl GameState for NewGameplay {
                 }));
 
                 text_box(TextBoxProps {
-                    text: format!("Tip word: {}", self.tip_content),
+                    text: format!(
+                        "Tip word: {} | Input: {} (F1) | Aim: {} (F2)",
+                        self.tip_content,
+                        self.player_controller.mode_label(),
+                        self.player_controller.aim_mode.name()
+                    ),
                     horizontal_align: TextBoxHorizontalAlign::Center,
                     vertical_align: TextBoxVerticalAlign::Middle,
                     font: TextBoxFont {
@@ -675,7 +917,11 @@ impl GameState for NewGameplay {
                 });
 
                 text_box(TextBoxProps {
-                    text: format!("Alive for {:.2} seconds", self.alive_time_seconds),
+                    text: format!(
+                        "Wave {} - alive for {:.2} seconds",
+                        self.wave_director.wave_number(),
+                        self.alive_time_seconds
+                    ),
                     horizontal_align: TextBoxHorizontalAlign::Center,
                     vertical_align: TextBoxVerticalAlign::Middle,
                     font: TextBoxFont {
@@ -692,39 +938,314 @@ impl GameState for NewGameplay {
                 });
             },
         );
+
+        if let Some((title, value, limit)) = self
+            .world
+            .query::<(&Boss, &Health)>()
+            .iter()
+            .next()
+            .map(|(_, (boss, health))| (boss.title.to_owned(), health.value, health.limit))
+        {
+            content_box(
+                ContentBoxItemLayout {
+                    anchors: Rect {
+                        left: 0.5,
+                        right: 0.5,
+                        top: 0.0,
+                        bottom: 0.0,
+                    },
+                    margin: Rect {
+                        left: -400.0,
+                        right: -400.0,
+                        top: 160.0,
+                        bottom: -220.0,
+                    },
+                    align: Vec2 { x: 0.5, y: 0.0 },
+                    ..Default::default()
+                },
+                || {
+                    health_bar(ContentBoxItemLayout::default(), value, limit);
+
+                    text_box(TextBoxProps {
+                        text: title.to_owned(),
+                        horizontal_align: TextBoxHorizontalAlign::Center,
+                        vertical_align: TextBoxVerticalAlign::Middle,
+                        font: TextBoxFont {
+                            name: "roboto".to_owned(),
+                            size: 36.0,
+                        },
+                        color: Color {
+                            r: 0.9,
+                            g: 0.9,
+                            b: 0.9,
+                            a: 1.0,
+                        },
+                        ..Default::default()
+                    });
+                },
+            );
+        }
+
+        if let Some((text, alpha)) = self.wave_director.announcement() {
+            content_box(
+                ContentBoxItemLayout {
+                    anchors: Rect {
+                        left: 0.5,
+                        right: 0.5,
+                        top: 0.3,
+                        bottom: 0.3,
+                    },
+                    margin: Rect {
+                        left: -300.0,
+                        right: -300.0,
+                        top: -60.0,
+                        bottom: -60.0,
+                    },
+                    align: Vec2 { x: 0.5, y: 0.5 },
+                    ..Default::default()
+                },
+                || {
+                    text_box(TextBoxProps {
+                        text: text.to_owned(),
+                        horizontal_align: TextBoxHorizontalAlign::Center,
+                        vertical_align: TextBoxVerticalAlign::Middle,
+                        font: TextBoxFont {
+                            name: "roboto".to_owned(),
+                            size: 100.0,
+                        },
+                        color: Color {
+                            r: 0.9,
+                            g: 0.1,
+                            b: 0.1,
+                            a: alpha,
+                        },
+                        ..Default::default()
+                    });
+                },
+            );
+        }
     }
 }
 
 impl NewGameplay {
-    pub fn cast_spell(world: &mut World, cast: PlayerCastAction, caster: Entity) {
+    pub fn new(audio: Audio, mode: GameMode) -> Self {
+        Self {
+            mode,
+            audio,
+            ..Default::default()
+        }
+    }
+
+    /// Casts spell and returns its spawned projectiles, or zone for spells
+    /// cast down at a point. Spells cast on oneself only buff the caster, or
+    /// fizzle when they do not buff, and spells with trigger are held back as
+    /// pending spell until it is met.
+    pub fn cast_spell(
+        world: &mut World,
+        audio: &mut Audio,
+        cast: PlayerCastAction,
+        caster: Entity,
+    ) -> Vec<Entity> {
         println!("=== CAST SPELL: {:#?}", cast.spell);
-        let mut transform = Transform::<f32, f32, f32>::default();
-        transform.position = cast.position.into();
+        let transform = Transform::<f32, f32, f32> {
+            position: cast.position.into(),
+            ..Default::default()
+        };
 
-        match cast.spell.shape {
-            SpellTagShape::Point => Self::cast_point_spell(world, &cast, &transform, caster),
-            SpellTagShape::Triangle => Self::cast_triangle_spell(world, &cast, &transform, caster),
-            SpellTagShape::Wall => Self::cast_wall_spell(world, &cast, &transform, caster),
+        let cast = match PendingSpell::new(cast, caster) {
+            Ok(pending) => return vec![Self::cast_pending_spell(world, audio, pending)],
+            Err(cast) => cast,
+        };
+
+        if cast.spell.target == SpellTagTarget::Oneself {
+            if let Some(buff) = Buff::from_spell(&cast.spell) {
+                BuffController::apply(world, caster, buff);
+                if let Some(mut sound) = audio.play("sound/spell") {
+                    let _ = sound.set_volume(0.5, Default::default());
+                }
+            }
+            return vec![];
+        }
+
+        // enemies have no summons.
+        let summon = cast
+            .spell
+            .summon
+            .filter(|_| world.satisfies::<&Player>(caster).unwrap_or_default());
+        let projectiles = if let Some(kind) = summon {
+            vec![Self::cast_summon_spell(world, &cast, kind, caster)]
+        } else {
+            match cast.spell.shape {
+                SpellTagShape::Point if cast.spell.direction == SpellTagDirection::Down => {
+                    vec![Self::cast_zone_spell(world, &cast, &transform, caster)]
+                }
+                SpellTagShape::Point => {
+                    vec![Self::cast_point_spell(world, &cast, &transform, caster)]
+                }
+                SpellTagShape::Triangle => {
+                    Self::cast_triangle_spell(world, &cast, &transform, caster)
+                }
+                SpellTagShape::Wall => {
+                    vec![Self::cast_wall_spell(world, &cast, &transform, caster)]
+                }
+            }
+        };
+
+        if let Some(mut sound) = audio.play("sound/spell") {
+            let _ = sound.set_volume(0.5, Default::default());
+        }
+
+        projectiles
+    }
+
+    /// Makes spawned spells friendly, so they do not hurt player and its summons.
+    pub fn befriend(world: &World, entities: &[Entity]) {
+        for entity in entities {
+            if let Ok(mut damage) = world.get::<&mut Damage>(*entity) {
+                damage.layer = DamageLayer::Player;
+            }
+            if let Ok(mut zone) = world.get::<&mut Zone>(*entity) {
+                zone.layer = DamageLayer::Player;
+            }
+            if let Ok(mut pending) = world.get::<&mut PendingSpell>(*entity) {
+                pending.layer = DamageLayer::Player;
+            }
+            if let Ok(mut wall) = world.get::<&mut Wall>(*entity) {
+                wall.layer = DamageLayer::Player;
+                if let Ok(mut health) = world.get::<&mut Health>(*entity) {
+                    health.layer = DamageLayer::Player;
+                }
+            }
         }
     }
 
+    fn cast_pending_spell(world: &mut World, audio: &mut Audio, pending: PendingSpell) -> Entity {
+        if let Some(mut sound) = audio.play("sound/spell") {
+            let _ = sound.set_volume(0.2, Default::default());
+        }
+        let spread = match pending.trigger {
+            SpellTagTrigger::Trap => PendingSpell::TRAP_RADIUS,
+            SpellTagTrigger::Delay | SpellTagTrigger::Hit => 0.0,
+        };
+
+        world.spawn((
+            Transform::<f32, f32, f32> {
+                position: pending.cast.position.into(),
+                ..Default::default()
+            },
+            ParticleGenerator {
+                emmission_accumulator: 0.0,
+                emmission_time: 0.3,
+                texture: pending.cast.spell.effect.texture().into(),
+                batch_size: 4,
+                spread: Vec2::new(spread, 0.0),
+            },
+            pending,
+        ))
+    }
+
+    fn cast_summon_spell(
+        world: &mut World,
+        cast: &PlayerCastAction,
+        kind: SpellTagSummon,
+        caster: Entity,
+    ) -> Entity {
+        SummonController::make_room(world, caster);
+        let radius = kind.radius();
+        let position = cast.position + cast.direction * (radius + 40.0);
+        let health = kind.health();
+
+        world.spawn((
+            Summon {
+                kind,
+                owner: caster,
+                effect: cast.spell.effect,
+                cooldown: kind.cooldown(),
+                alive_time: 0.0,
+                ttl: cast.spell.duration.time() * Summon::LIFETIME_MULTIPLIER,
+            },
+            Transform::<f32, f32, f32> {
+                position: position.into(),
+                ..Default::default()
+            },
+            Collidable {
+                space_object: Some(SpaceObject {
+                    entity: None,
+                    position,
+                    collider: SpaceCollider::circle(radius),
+                }),
+            },
+            Health {
+                value: health,
+                limit: health,
+                layer: DamageLayer::Player,
+            },
+            Effect::default(),
+            Immobility { time_left: 0.0 },
+            Velocity::default(),
+            Knockback::new(kind.mass()),
+            SpriteData {
+                texture: "player/0".into(),
+                tint: Effect::from(cast.spell.effect).slime_tint(),
+                scale: (radius / 40.0).into(),
+                ..Default::default()
+            },
+        ))
+    }
+
     fn cast_wall_spell(
         world: &mut World,
         cast: &PlayerCastAction,
         transform: &Transform<f32, f32, f32>,
         caster: Entity,
-    ) {
+    ) -> Entity {
         let perpendicular_direction = Vec2::new(-cast.direction.y, cast.direction.x);
         let count = 5;
-        let start = cast.position
-            - perpendicular_direction * cast.spell.size.radius() * (count as f32) / 2.0;
-
-        for i in 0..count {
-            let mut new_transform = transform.clone();
-            new_transform.position =
-                (start + perpendicular_direction * cast.spell.size.radius() * i as f32).into();
-            Self::cast_point_spell(world, cast, &new_transform, caster);
+        let half_length = cast.spell.size.radius() * (count as f32) / 2.0;
+        // walls cast at the caster are raised in front of it.
+        let mut transform = *transform;
+        if cast.spell.target == SpellTagTarget::Caster {
+            transform.position += Vec3::from(cast.direction * Wall::DISTANCE);
         }
+        let health = cast.spell.size.radius();
+        let layer = world
+            .get::<&Health>(caster)
+            .map(|health| health.layer)
+            .unwrap_or_default();
+
+        world.spawn((
+            Wall {
+                layer,
+                ..Wall::new(cast.spell.effect, caster, cast.spell.duration.time())
+            },
+            Obstacle {
+                projectile_response: ObstacleProjectileResponse::Stop,
+            },
+            Health {
+                value: health,
+                limit: health,
+                layer,
+            },
+            transform,
+            Collidable {
+                space_object: Some(SpaceObject {
+                    entity: None,
+                    position: transform.position.xy(),
+                    collider: SpaceCollider::capsule(
+                        Wall::THICKNESS,
+                        half_length,
+                        perpendicular_direction,
+                    ),
+                }),
+            },
+            ParticleGenerator {
+                emmission_accumulator: 0.0,
+                emmission_time: 0.1,
+                texture: cast.spell.effect.texture().into(),
+                batch_size: Wall::PARTICLES,
+                spread: perpendicular_direction * half_length,
+            },
+        ))
     }
 
     fn cast_triangle_spell(
@@ -732,7 +1253,7 @@ impl NewGameplay {
         cast: &PlayerCastAction,
         transform: &Transform<f32, f32, f32>,
         caster: Entity,
-    ) {
+    ) -> Vec<Entity> {
         let angle = cast.direction.y.atan2(cast.direction.x);
         let left = angle - std::f32::consts::FRAC_PI_3 + std::f32::consts::FRAC_PI_2;
         let right = angle + std::f32::consts::FRAC_PI_3 + std::f32::consts::FRAC_PI_2;
@@ -742,31 +1263,58 @@ impl NewGameplay {
         let end_direction = Vec2::<f32>::new(end.cos(), end.sin());
         let count = 5;
         let start = cast.position;
+        let mut projectiles = Vec::with_capacity(count * 3);
 
         for i in 0..count {
-            let mut new_transform = transform.clone();
+            let mut new_transform = *transform;
             new_transform.position =
                 (start + left_direction * cast.spell.size.radius() * i as f32).into();
-            Self::cast_point_spell(world, cast, &new_transform, caster);
+            projectiles.push(Self::cast_point_spell(world, cast, &new_transform, caster));
 
             if i == 0 {
                 continue;
             }
 
-            let mut new_transform = transform.clone();
+            let mut new_transform = *transform;
             new_transform.position =
                 (start - right_direction * cast.spell.size.radius() * i as f32).into();
-            Self::cast_point_spell(world, cast, &new_transform, caster);
+            projectiles.push(Self::cast_point_spell(world, cast, &new_transform, caster));
         }
 
         for i in 0..(count + 1) {
-            let mut new_transform = transform.clone();
+            let mut new_transform = *transform;
             new_transform.position = ((start
                 + left_direction * cast.spell.size.radius() * count as f32)
                 + end_direction * cast.spell.size.radius() * i as f32)
                 .into();
-            Self::cast_point_spell(world, cast, &new_transform, caster);
+            projectiles.push(Self::cast_point_spell(world, cast, &new_transform, caster));
         }
+
+        projectiles
+    }
+
+    fn cast_zone_spell(
+        world: &mut World,
+        cast: &PlayerCastAction,
+        transform: &Transform<f32, f32, f32>,
+        caster: Entity,
+    ) -> Entity {
+        // zones stay on the caster's side, so enemies do not burn each other.
+        let layer = world
+            .get::<&Health>(caster)
+            .map(|health| health.layer)
+            .unwrap_or_default();
+        world.spawn((
+            *transform,
+            Zone::from_spell(&cast.spell, caster, layer),
+            ParticleGenerator {
+                emmission_accumulator: 0.0,
+                emmission_time: 0.2,
+                texture: cast.spell.effect.texture().into(),
+                batch_size: 8,
+                spread: Vec2::new(cast.spell.size.radius(), 0.0),
+            },
+        ))
     }
 
     fn cast_point_spell(
@@ -774,47 +1322,55 @@ impl NewGameplay {
         cast: &PlayerCastAction,
         transform: &Transform<f32, f32, f32>,
         caster: Entity,
-    ) {
-        let mut sound = Audio::write().write().unwrap().play("sound/spell").unwrap();
-        let _ = sound.set_volume(0.5, Default::default());
+    ) -> Entity {
+        Self::spawn_spell(
+            world,
+            cast,
+            transform,
+            caster,
+            SpaceCollider::circle(10.0),
+            ParticleGenerator {
+                emmission_accumulator: 0.0,
+                emmission_time: 0.1,
+                texture: cast.spell.effect.texture().into(),
+                batch_size: 16,
+                spread: Vec2::zero(),
+            },
+        )
+    }
 
+    fn spawn_spell(
+        world: &mut World,
+        cast: &PlayerCastAction,
+        transform: &Transform<f32, f32, f32>,
+        caster: Entity,
+        collider: SpaceCollider,
+        particle_generator: ParticleGenerator,
+    ) -> Entity {
         world.spawn((
             Animation { animation: None },
             Effect::from(cast.spell.effect),
-            transform.clone(),
-            Projectile::new(
-                match cast.spell.speed {
-                    SpellTagSpeed::Fast => 1000.0,
-                    SpellTagSpeed::Medium => 500.0,
-                    SpellTagSpeed::Slow => 100.0,
-                },
-                cast.direction,
-                cast.spell.duration.time(),
-            ),
+            *transform,
+            Projectile {
+                caster: Some(caster),
+                ..Projectile::new(
+                    match cast.spell.speed {
+                        SpellTagSpeed::Fast => 1000.0,
+                        SpellTagSpeed::Medium => 500.0,
+                        SpellTagSpeed::Slow => 100.0,
+                    },
+                    cast.direction,
+                    cast.spell.duration.time(),
+                )
+            },
             Collidable {
                 space_object: Some(SpaceObject {
                     entity: None,
                     position: Vec2::default(),
-                    collider_radius: 10.0,
+                    collider,
                 }),
             },
-            // SpriteData {
-            //     texture: match cast.spell.effect {
-            //         SpellTagEffect::None => "particle/smoke".into(),
-            //         SpellTagEffect::Fire => "particle/fire".into(),
-            //         SpellTagEffect::Electric => "particle/electric".into(),
-            //         SpellTagEffect::Water => "particle/water".into(),
-            //     },
-            //     shader: "image".into(),
-            //     pivot: 0.5.into(),
-            //     tint: Rgba::white(),
-            // },
-            ParticleGenerator {
-                emmission_accumulator: 0.0,
-                emmission_time: 0.1,
-                texture: cast.spell.effect.texture().into(),
-                batch_size: 16,
-            },
+            particle_generator,
             Damage {
                 value: cast.spell.damage.damage(),
                 layer: DamageLayer::None,
@@ -824,6 +1380,49 @@ impl NewGameplay {
                 ignored_entity: caster,
             },
             cast.spell.clone(),
-        ));
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::components::spell::Spell;
+
+    #[test]
+    fn test_enemy_words_are_known() {
+        let gameplay = NewGameplay::default();
+        let database = &gameplay.word_to_spell_tag_database;
+        for (name, kind) in &gameplay.enemy_database.kinds {
+            for word in kind.words.keys() {
+                assert!(
+                    database.records.contains_key(word),
+                    "{} speaks unknown word: {}",
+                    name,
+                    word
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_zones_take_side_of_caster() {
+        let mut world = World::new();
+        let cast = PlayerCastAction {
+            position: Vec2::zero(),
+            direction: Vec2::unit_x(),
+            spell: Spell::basic(),
+        };
+        let caster = world.spawn((Health {
+            value: 10.0,
+            limit: 10.0,
+            layer: DamageLayer::Enemy,
+        },));
+        let zone = NewGameplay::cast_zone_spell(&mut world, &cast, &Default::default(), caster);
+        assert_eq!(world.get::<&Zone>(zone).unwrap().layer, DamageLayer::Enemy);
+
+        let caster = world.spawn(());
+        let zone = NewGameplay::cast_zone_spell(&mut world, &cast, &Default::default(), caster);
+        assert_eq!(world.get::<&Zone>(zone).unwrap().layer, DamageLayer::None);
     }
 }
@@ -1,6 +1,6 @@
 use crate::game::utils::audio::Audio;
 
-use super::new_gameplay::NewGameplay;
+use super::main_menu::MainMenu;
 use micro_games_kit::{
     context::GameContext,
     game::{GameState, GameStateChange},
@@ -23,10 +23,10 @@ impl GameState for Preloader {
         Self::load_shaders(&mut context);
         Self::load_fonts(&mut context);
         Self::load_textures(&mut context);
-        Self::load_sounds_and_music();
+        let audio = Self::load_sounds_and_music();
         Self::setup_gui_inputs(&mut context);
 
-        *context.state_change = GameStateChange::Swap(Box::new(NewGameplay::default()));
+        *context.state_change = GameStateChange::Swap(Box::new(MainMenu::new(audio)));
     }
 }
 
@@ -98,6 +98,32 @@ impl Preloader {
             1,
         );
 
+        // obstacles
+        load_texture(
+            context.draw,
+            context.graphics,
+            "obstacle/rock",
+            include_bytes!("../../../assets/images/obstacles/rock.png"),
+            1,
+            1,
+        );
+        load_texture(
+            context.draw,
+            context.graphics,
+            "obstacle/tree",
+            include_bytes!("../../../assets/images/obstacles/tree.png"),
+            1,
+            1,
+        );
+        load_texture(
+            context.draw,
+            context.graphics,
+            "obstacle/ruin",
+            include_bytes!("../../../assets/images/obstacles/ruin.png"),
+            1,
+            1,
+        );
+
         // player character
         load_texture(
             context.draw,
@@ -328,6 +354,74 @@ impl Preloader {
             1,
         );
 
+        // zones
+        load_texture(
+            context.draw,
+            context.graphics,
+            "zone/fire",
+            include_bytes!("../../../assets/images/zones/fire.png"),
+            1,
+            1,
+        );
+        load_texture(
+            context.draw,
+            context.graphics,
+            "zone/water",
+            include_bytes!("../../../assets/images/zones/water.png"),
+            1,
+            1,
+        );
+        load_texture(
+            context.draw,
+            context.graphics,
+            "zone/electric",
+            include_bytes!("../../../assets/images/zones/electric.png"),
+            1,
+            1,
+        );
+        load_texture(
+            context.draw,
+            context.graphics,
+            "zone/none",
+            include_bytes!("../../../assets/images/zones/none.png"),
+            1,
+            1,
+        );
+        load_texture(
+            context.draw,
+            context.graphics,
+            "zone/steam",
+            include_bytes!("../../../assets/images/zones/steam.png"),
+            1,
+            1,
+        );
+        load_texture(
+            context.draw,
+            context.graphics,
+            "zone/paralized",
+            include_bytes!("../../../assets/images/zones/paralized.png"),
+            1,
+            1,
+        );
+        load_texture(
+            context.draw,
+            context.graphics,
+            "zone/explosion",
+            include_bytes!("../../../assets/images/zones/explosion.png"),
+            1,
+            1,
+        );
+
+        // pickups
+        load_texture(
+            context.draw,
+            context.graphics,
+            "pickup/health",
+            include_bytes!("../../../assets/images/pickup/health.png"),
+            1,
+            1,
+        );
+
         // ui
         load_texture(
             context.draw,
@@ -353,20 +447,73 @@ impl Preloader {
             1,
             1,
         );
+        load_texture(
+            context.draw,
+            context.graphics,
+            "ui/button/idle",
+            include_bytes!("../../../assets/images/ui/button/idle.png"),
+            1,
+            1,
+        );
+        load_texture(
+            context.draw,
+            context.graphics,
+            "ui/button/select",
+            include_bytes!("../../../assets/images/ui/button/select.png"),
+            1,
+            1,
+        );
+        load_texture(
+            context.draw,
+            context.graphics,
+            "ui/button/trigger",
+            include_bytes!("../../../assets/images/ui/button/trigger.png"),
+            1,
+            1,
+        );
+        load_texture(
+            context.draw,
+            context.graphics,
+            "ui/buff/heal",
+            include_bytes!("../../../assets/images/ui/buff/heal.png"),
+            1,
+            1,
+        );
+        load_texture(
+            context.draw,
+            context.graphics,
+            "ui/buff/haste",
+            include_bytes!("../../../assets/images/ui/buff/haste.png"),
+            1,
+            1,
+        );
+        load_texture(
+            context.draw,
+            context.graphics,
+            "ui/buff/shield",
+            include_bytes!("../../../assets/images/ui/buff/shield.png"),
+            1,
+            1,
+        );
     }
 
-    fn load_sounds_and_music() {
-        let mut audio = Audio::write();
-        let mut audio = audio.write().unwrap();
+    fn load_sounds_and_music() -> Audio {
+        let mut audio = Audio::new();
 
         audio.register(
             "sound/spell",
             include_bytes!("../../../assets/sounds/spell.ogg"),
         );
+        audio.register(
+            "sound/clash",
+            include_bytes!("../../../assets/sounds/clash.wav"),
+        );
         audio.register(
             "music/ambient",
             include_bytes!("../../../assets/music/ambient.ogg"),
         );
+
+        audio
     }
 
     fn setup_gui_inputs(context: &mut GameContext) {
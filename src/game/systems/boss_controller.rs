@@ -0,0 +1,226 @@
+use super::player_controller::PlayerCastAction;
+use crate::game::{
+    components::{
+        boss::{Boss, BossAttack, BossPattern},
+        effect::Effect,
+        health::Health,
+        homing::Homing,
+        player::Player,
+    },
+    states::new_gameplay::NewGameplay,
+    utils::{
+        audio::Audio,
+        magic::spell_tag::{SpellTagShape, SpellTagTarget},
+    },
+};
+use hecs::{Entity, World};
+use micro_games_kit::third_party::vek::{Transform, Vec2};
+
+pub struct BossController;
+
+impl BossController {
+    pub fn run(world: &mut World, audio: &mut Audio, delta_time: f32) {
+        let Some((player, player_position)) = world
+            .query::<&Transform<f32, f32, f32>>()
+            .with::<&Player>()
+            .iter()
+            .next()
+            .map(|(entity, transform)| (entity, transform.position.xy()))
+        else {
+            return;
+        };
+
+        let mut attacks = Vec::<(Entity, Vec2<f32>, Option<Effect>, BossAttack)>::new();
+
+        for (entity, (boss, health, transform, effect)) in world
+            .query::<(
+                &mut Boss,
+                &Health,
+                &Transform<f32, f32, f32>,
+                Option<&Effect>,
+            )>()
+            .iter()
+        {
+            let phase = boss.phase_for_health(health.value / health.limit.max(f32::EPSILON));
+            if phase != boss.phase {
+                // new phase opens with its first attack right away.
+                boss.phase = phase;
+                boss.attack = 0;
+                boss.cooldown = 0.0;
+            }
+            let Some(phase) = boss.phases.get(boss.phase) else {
+                continue;
+            };
+            if phase.attacks.is_empty() {
+                continue;
+            }
+
+            boss.cooldown -= delta_time;
+            if boss.cooldown > 0.0 {
+                continue;
+            }
+            boss.cooldown = phase.interval;
+            let attack = phase.attacks[boss.attack % phase.attacks.len()];
+            boss.attack = (boss.attack + 1) % phase.attacks.len();
+            attacks.push((entity, transform.position.xy(), effect.copied(), attack));
+        }
+
+        for (entity, position, effect, attack) in attacks {
+            let to_player = (player_position - position)
+                .try_normalized()
+                .unwrap_or(Vec2::unit_x());
+            let mut spell = attack.spell.to_spell(effect.as_ref());
+
+            match attack.pattern {
+                BossPattern::Ring { count } => {
+                    for index in 0..count {
+                        let angle = std::f32::consts::TAU * index as f32 / count as f32;
+                        NewGameplay::cast_spell(
+                            world,
+                            audio,
+                            PlayerCastAction {
+                                direction: to_player.rotated_z(angle),
+                                position,
+                                spell: spell.clone(),
+                            },
+                            entity,
+                        );
+                    }
+                }
+                BossPattern::Wall => {
+                    // raised right where the player stands, not in front of
+                    // the boss.
+                    spell.shape = SpellTagShape::Wall;
+                    spell.target = SpellTagTarget::Cursor;
+                    NewGameplay::cast_spell(
+                        world,
+                        audio,
+                        PlayerCastAction {
+                            direction: to_player,
+                            position: player_position,
+                            spell,
+                        },
+                        entity,
+                    );
+                }
+                BossPattern::Homing {
+                    count,
+                    spread,
+                    turn_rate,
+                } => {
+                    let first = -spread.to_radians() * (count.saturating_sub(1)) as f32 * 0.5;
+                    for index in 0..count {
+                        let angle = first + spread.to_radians() * index as f32;
+                        let projectiles = NewGameplay::cast_spell(
+                            world,
+                            audio,
+                            PlayerCastAction {
+                                direction: to_player.rotated_z(angle),
+                                position,
+                                spell: spell.clone(),
+                            },
+                            entity,
+                        );
+                        for projectile in projectiles {
+                            let _ = world.insert_one(
+                                projectile,
+                                Homing {
+                                    target: player,
+                                    turn_rate,
+                                },
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{
+        components::{damage::DamageLayer, projectile::Projectile, wall::Wall},
+        utils::{enemies::BossData, test_utils::player},
+    };
+    use micro_games_kit::third_party::toml;
+
+    #[test]
+    fn test_boss_phases() {
+        let boss = toml::from_str::<BossData>(
+            r#"
+            title = "King"
+
+            [[phases]]
+            health = 1.0
+            interval = 1.0
+            attacks = [
+                { pattern = "ring", count = 8 },
+                { pattern = "wall", spell = { size = "large" } },
+            ]
+
+            [[phases]]
+            health = 0.5
+            interval = 0.5
+            attacks = [
+                { pattern = "homing", count = 3, spread = 20.0, turn_rate = 1.0 },
+            ]
+            "#,
+        )
+        .unwrap();
+
+        let mut world = World::new();
+        let mut audio = Audio::default();
+        world.spawn((
+            player(),
+            Transform::<f32, f32, f32> {
+                position: Vec2::new(500.0, 0.0).into(),
+                ..Default::default()
+            },
+        ));
+        let boss = world.spawn((
+            Boss::new(boss.title, boss.phases),
+            Health {
+                value: 100.0,
+                limit: 100.0,
+                layer: DamageLayer::Enemy,
+            },
+            Transform::<f32, f32, f32>::default(),
+        ));
+        let projectiles = |world: &World| world.query::<&Projectile>().iter().count();
+        let clear = |world: &mut World| {
+            let entities = world
+                .query::<&Projectile>()
+                .iter()
+                .map(|(entity, _)| entity)
+                .collect::<Vec<_>>();
+            for entity in entities {
+                world.despawn(entity).unwrap();
+            }
+        };
+
+        // first phase opens with a ring.
+        BossController::run(&mut world, &mut audio, 0.1);
+        assert_eq!(projectiles(&world), 8);
+        clear(&mut world);
+
+        // then waits for its interval and throws a wall.
+        BossController::run(&mut world, &mut audio, 0.5);
+        assert_eq!(projectiles(&world), 0);
+        BossController::run(&mut world, &mut audio, 0.6);
+        let walls = world
+            .query::<(&Wall, &Transform<f32, f32, f32>)>()
+            .iter()
+            .map(|(_, (_, transform))| transform.position.xy())
+            .collect::<Vec<_>>();
+        assert_eq!(walls, vec![Vec2::new(500.0, 0.0)]);
+        clear(&mut world);
+
+        // dropping below threshold switches to homing volleys immediately.
+        world.get::<&mut Health>(boss).unwrap().value = 40.0;
+        BossController::run(&mut world, &mut audio, 0.1);
+        assert_eq!(world.get::<&Boss>(boss).unwrap().phase, 1);
+        assert_eq!(world.query::<(&Projectile, &Homing)>().iter().count(), 3);
+    }
+}
@@ -0,0 +1,172 @@
+use crate::game::components::{
+    affix::Shielded,
+    buff::{Buff, BuffKind, Buffs},
+    health::Health,
+};
+use hecs::{Entity, World};
+
+pub struct BuffController;
+
+impl BuffController {
+    pub fn run(world: &mut World, delta_time: f32) {
+        let mut expired = Vec::<(Entity, Buff)>::new();
+
+        for (entity, (buffs, mut health)) in
+            world.query::<(&mut Buffs, Option<&mut Health>)>().iter()
+        {
+            for buff in &mut buffs.active {
+                let time = delta_time.min(buff.time_left);
+                buff.time_left -= delta_time;
+                if buff.kind == BuffKind::Heal {
+                    if let Some(health) = health.as_deref_mut() {
+                        health.value = (health.value + buff.value * time).min(health.limit);
+                    }
+                }
+                if buff.time_left <= 0.0 {
+                    expired.push((entity, *buff));
+                }
+            }
+            buffs.active.retain(|buff| buff.time_left > 0.0);
+        }
+
+        for (entity, buff) in expired {
+            Self::remove_shield(world, entity, &buff);
+        }
+    }
+
+    /// Puts buff on entity, replacing one of the same kind.
+    pub fn apply(world: &mut World, entity: Entity, buff: Buff) {
+        if !world.satisfies::<&Buffs>(entity).unwrap_or(true) {
+            let _ = world.insert_one(entity, Buffs::default());
+        }
+        let previous = {
+            let Ok(mut buffs) = world.get::<&mut Buffs>(entity) else {
+                return;
+            };
+            let previous = buffs.get(buff.kind).copied();
+            buffs.active.retain(|active| active.kind != buff.kind);
+            buffs.active.push(buff);
+            previous
+        };
+
+        if buff.kind == BuffKind::Shield {
+            if let Some(previous) = previous {
+                Self::remove_shield(world, entity, &previous);
+            }
+            if let Ok(mut shielded) = world.get::<&mut Shielded>(entity) {
+                shielded.value += buff.value;
+                return;
+            }
+            let _ = world.insert_one(entity, Shielded { value: buff.value });
+        }
+    }
+
+    // Takes away what is left of shield given by the buff.
+    fn remove_shield(world: &World, entity: Entity, buff: &Buff) {
+        if buff.kind != BuffKind::Shield {
+            return;
+        }
+        if let Ok(mut shielded) = world.get::<&mut Shielded>(entity) {
+            shielded.value = (shielded.value - buff.value).max(0.0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{
+        components::{damage::DamageLayer, spell::Spell},
+        states::new_gameplay::NewGameplay,
+        systems::player_controller::PlayerCastAction,
+        utils::{
+            audio::Audio,
+            magic::spell_tag::{
+                SpellTagDuration, SpellTagEffect, SpellTagShape, SpellTagSize, SpellTagTarget,
+            },
+        },
+    };
+    use micro_games_kit::third_party::vek::{Transform, Vec2};
+
+    #[test]
+    fn test_self_buffs() {
+        let spell = |shape, effect| Spell {
+            shape,
+            effect,
+            size: SpellTagSize::Medium,
+            duration: SpellTagDuration::Quick,
+            target: SpellTagTarget::Oneself,
+            ..Spell::basic()
+        };
+        let heal = Buff::from_spell(&spell(SpellTagShape::Point, SpellTagEffect::Water)).unwrap();
+        let haste =
+            Buff::from_spell(&spell(SpellTagShape::Point, SpellTagEffect::Electric)).unwrap();
+        let shield = Buff::from_spell(&spell(SpellTagShape::Wall, SpellTagEffect::Fire)).unwrap();
+        assert_eq!(heal.kind, BuffKind::Heal);
+        assert_eq!(haste.kind, BuffKind::Haste);
+        assert_eq!(shield.kind, BuffKind::Shield);
+        assert_eq!(heal.duration, 1.5);
+        assert_eq!(
+            Buff::from_spell(&spell(SpellTagShape::Point, SpellTagEffect::Fire)),
+            None
+        );
+        assert_eq!(Buff::from_spell(&Spell::basic()), None);
+
+        let mut world = World::new();
+        let entity = world.spawn((Health {
+            value: 50.0,
+            limit: 100.0,
+            layer: DamageLayer::Player,
+        },));
+        BuffController::apply(&mut world, entity, heal);
+        BuffController::apply(&mut world, entity, haste);
+        BuffController::apply(&mut world, entity, shield);
+        BuffController::apply(&mut world, entity, shield);
+        assert_eq!(world.get::<&Buffs>(entity).unwrap().active.len(), 3);
+        assert_eq!(world.get::<&Buffs>(entity).unwrap().speed_multiplier(), 1.5);
+        // recast shield replaces the old one instead of stacking.
+        assert_eq!(world.get::<&Shielded>(entity).unwrap().value, 40.0);
+
+        // heal spreads its strength over whole duration, then all expire.
+        BuffController::run(&mut world, 0.75);
+        assert!((world.get::<&Health>(entity).unwrap().value - 70.0).abs() < 1.0e-3);
+        BuffController::run(&mut world, 1.0);
+        assert!((world.get::<&Health>(entity).unwrap().value - 90.0).abs() < 1.0e-3);
+        assert!(world.get::<&Buffs>(entity).unwrap().active.is_empty());
+        assert_eq!(world.get::<&Shielded>(entity).unwrap().value, 0.0);
+    }
+
+    #[test]
+    fn test_self_spells_without_buff_fizzle() {
+        let mut world = World::new();
+        let mut audio = Audio::default();
+        let caster = world.spawn((
+            Transform::<f32, f32, f32>::default(),
+            Health {
+                value: 100.0,
+                limit: 100.0,
+                layer: DamageLayer::Player,
+            },
+        ));
+        let cast = |effect| PlayerCastAction {
+            direction: Vec2::unit_x(),
+            position: Vec2::zero(),
+            spell: Spell {
+                effect,
+                target: SpellTagTarget::Oneself,
+                ..Spell::basic()
+            },
+        };
+
+        for effect in [SpellTagEffect::Fire, SpellTagEffect::None] {
+            let spawned = NewGameplay::cast_spell(&mut world, &mut audio, cast(effect), caster);
+            assert!(spawned.is_empty());
+        }
+        assert_eq!(world.len(), 1);
+        assert!(world.get::<&Buffs>(caster).is_err());
+
+        NewGameplay::cast_spell(&mut world, &mut audio, cast(SpellTagEffect::Water), caster);
+        assert_eq!(world.len(), 1);
+        assert_eq!(world.get::<&Buffs>(caster).unwrap().active.len(), 1);
+    }
+}
@@ -5,7 +5,7 @@ use micro_games_kit::third_party::vek::Transform;
 pub struct CollisionDetector;
 
 impl CollisionDetector {
-    pub fn run(world: &World) {
+    pub fn run(world: &World, space: &mut Space) {
         // Update space oobject positions
         for (id, (collidable, transform)) in world
             .query::<(&mut Collidable, &Transform<f32, f32, f32>)>()
@@ -17,12 +17,12 @@ impl CollisionDetector {
             }
         }
 
-        // Clone space_object and pass them to Space maintaint
-        let space_objects = world
-            .query::<(&Collidable,)>()
-            .iter()
-            .filter_map(|(_, (collidable,))| collidable.space_object.as_ref().cloned())
-            .collect();
-        Space::write().write().unwrap().maintain(space_objects);
+        // Pass space objects to Space maintain, only changed ones get reinserted
+        space.maintain(
+            world
+                .query::<(&Collidable,)>()
+                .iter()
+                .filter_map(|(_, (collidable,))| collidable.space_object.as_ref().cloned()),
+        );
     }
 }
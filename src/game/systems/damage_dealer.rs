@@ -1,6 +1,12 @@
 use crate::game::{
     components::{
-        collidable::Collidable, damage::Damage, health::Health, ignore_entity::IgnoreEntity,
+        affix::{Reflective, Shielded, Vampiric},
+        collidable::Collidable,
+        damage::Damage,
+        health::Health,
+        ignore_entity::IgnoreEntity,
+        pending_spell::PendingSpell,
+        wall::Wall,
     },
     utils::space::Space,
 };
@@ -9,10 +15,8 @@ use hecs::{Entity, World};
 pub struct DamageDealer;
 
 impl DamageDealer {
-    pub fn run(world: &World) {
-        let space = Space::read();
-        let space = space.read().unwrap();
-        let mut entities_to_damage = Vec::<(Damage, Entity)>::new();
+    pub fn run(world: &World, space: &Space) {
+        let mut entities_to_damage = Vec::<(Entity, Damage, Entity)>::new();
 
         for (entity_a, (collidable, damage)) in world.query::<(&Collidable, &Damage)>().iter() {
             if let Some(space_object) = collidable.space_object.as_ref() {
@@ -22,26 +26,157 @@ impl DamageDealer {
                             if IgnoreEntity::should_be_ignored(world, entity_a, entity_b) {
                                 continue;
                             }
+                            if Reflective::reflects(world, entity_a, entity_b) {
+                                continue;
+                            }
+                            // spells passing through a wall leave it intact.
+                            if Wall::passes(world, entity_b, entity_a) {
+                                continue;
+                            }
 
-                            entities_to_damage.push((damage.clone(), entity_b));
+                            entities_to_damage.push((entity_a, *damage, entity_b));
                         }
                     }
                 }
             }
         }
 
-        for (damage, entity) in entities_to_damage {
-            let mut query = world.query::<&mut Health>();
+        for (source, damage, entity) in entities_to_damage {
+            Self::deal(world, source, damage, entity);
+        }
+    }
+
+    /// Hurts entity through its shield, vampiric source heals by part of it
+    /// and spells of entity waiting for a hit get armed against the source.
+    /// Returns damage taken by health.
+    pub fn deal(world: &World, source: Entity, damage: Damage, entity: Entity) -> f32 {
+        let dealt = {
+            let mut query = world.query::<(&mut Health, Option<&mut Shielded>)>();
             let mut view = query.view();
             let [entity_query] = view.get_mut_n([entity]);
+            let Some((health, shielded)) = entity_query else {
+                return 0.0;
+            };
+            if !damage.layer.allow_damage(health.layer) {
+                return 0.0;
+            }
 
-            if let Some(health) = entity_query {
-                if !damage.layer.allow_damage(health.layer) {
-                    continue;
-                }
+            let mut value = damage.value;
+            if let Some(shielded) = shielded {
+                let absorbed = value.min(shielded.value);
+                shielded.value -= absorbed;
+                value -= absorbed;
+            }
+            let dealt = value.min(health.value.max(0.0));
+            health.value -= value;
+            dealt
+        };
+        PendingSpell::notify_hit(world, entity, source);
 
-                health.value -= damage.value;
+        if let Ok(vampiric) = world.get::<&Vampiric>(source) {
+            if let Ok(mut health) = world.get::<&mut Health>(source) {
+                health.value = (health.value + dealt * vampiric.fraction).min(health.limit);
             }
         }
+        dealt
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{
+        components::damage::DamageLayer,
+        systems::collision_detector::CollisionDetector,
+        utils::{space::SpaceCollider, test_utils::collidable},
+    };
+    use micro_games_kit::third_party::vek::{Transform, Vec2};
+
+    fn spawn_colliding_pair(world: &mut World, offset: f32) -> Entity {
+        world.spawn((
+            Transform::<f32, f32, f32>::default(),
+            collidable(SpaceCollider::circle(10.0)),
+            Damage {
+                value: 5.0,
+                layer: DamageLayer::Enemy,
+            },
+        ));
+        world.spawn((
+            Transform::<f32, f32, f32> {
+                position: Vec2::new(offset, 0.0).into(),
+                ..Default::default()
+            },
+            collidable(SpaceCollider::circle(10.0)),
+            Health {
+                value: 50.0,
+                limit: 50.0,
+                layer: DamageLayer::None,
+            },
+        ))
+    }
+
+    #[test]
+    fn test_independent_simulations() {
+        let mut world_a = World::new();
+        let mut space_a = Space::default();
+        let target_a = spawn_colliding_pair(&mut world_a, 15.0);
+
+        let mut world_b = World::new();
+        let mut space_b = Space::default();
+        let target_b = spawn_colliding_pair(&mut world_b, 100.0);
+
+        for _ in 0..2 {
+            CollisionDetector::run(&world_a, &mut space_a);
+            CollisionDetector::run(&world_b, &mut space_b);
+            DamageDealer::run(&world_a, &space_a);
+            DamageDealer::run(&world_b, &space_b);
+        }
+
+        assert_eq!(world_a.get::<&Health>(target_a).unwrap().value, 40.0);
+        assert_eq!(world_b.get::<&Health>(target_b).unwrap().value, 50.0);
+    }
+
+    #[test]
+    fn test_shielded_and_vampiric() {
+        let mut world = World::new();
+        let mut space = Space::default();
+        let attacker = world.spawn((
+            Transform::<f32, f32, f32>::default(),
+            collidable(SpaceCollider::circle(10.0)),
+            Damage {
+                value: 5.0,
+                layer: DamageLayer::Enemy,
+            },
+            Health {
+                value: 10.0,
+                limit: 20.0,
+                layer: DamageLayer::Enemy,
+            },
+            Vampiric { fraction: 0.5 },
+        ));
+        let target = world.spawn((
+            Transform::<f32, f32, f32> {
+                position: Vec2::new(15.0, 0.0).into(),
+                ..Default::default()
+            },
+            collidable(SpaceCollider::circle(10.0)),
+            Health {
+                value: 50.0,
+                limit: 50.0,
+                layer: DamageLayer::None,
+            },
+            Shielded { value: 7.0 },
+        ));
+
+        // shield soaks first hit and part of second one, nothing to drain from it.
+        CollisionDetector::run(&world, &mut space);
+        DamageDealer::run(&world, &space);
+        assert_eq!(world.get::<&Shielded>(target).unwrap().value, 2.0);
+        assert_eq!(world.get::<&Health>(target).unwrap().value, 50.0);
+        assert_eq!(world.get::<&Health>(attacker).unwrap().value, 10.0);
+        DamageDealer::run(&world, &space);
+        assert_eq!(world.get::<&Shielded>(target).unwrap().value, 0.0);
+        assert_eq!(world.get::<&Health>(target).unwrap().value, 47.0);
+        assert_eq!(world.get::<&Health>(attacker).unwrap().value, 11.5);
     }
 }
@@ -1,19 +1,225 @@
-use crate::game::components::health::Health;
+use super::player_controller::PlayerCastAction;
+use crate::game::{
+    components::{
+        collidable::Collidable,
+        effect::Effect,
+        health::Health,
+        on_death::{DeathBehaviour, OnDeath},
+        particle_generator::ParticleGenerator,
+        pickup::Pickup,
+        spell::Spell,
+        sprite_data::SpriteData,
+        zone::Zone,
+    },
+    states::new_gameplay::NewGameplay,
+    utils::{
+        audio::Audio,
+        enemies::EnemyDatabase,
+        magic::spell_tag::{SpellTagDirection, SpellTagDuration, SpellTagEffect, SpellTagShape},
+        space::{SpaceCollider, SpaceObject},
+    },
+};
 use hecs::World;
+use micro_games_kit::third_party::{
+    rand::{thread_rng, Rng},
+    vek::{Transform, Vec2},
+};
 
 pub struct Death;
 
 impl Death {
-    pub fn run(world: &mut World) {
+    pub fn run(world: &mut World, audio: &mut Audio, enemy_database: &EnemyDatabase) {
         let to_destroy = world
-            .query::<&Health>()
+            .query::<(
+                &Health,
+                Option<&Transform<f32, f32, f32>>,
+                Option<&Effect>,
+                Option<&OnDeath>,
+            )>()
             .iter()
-            .filter(|(_, health)| health.value <= 0.0)
-            .map(|(entity, _)| entity)
+            .filter(|(_, (health, ..))| health.value <= 0.0)
+            .map(|(entity, (_, transform, effect, on_death))| {
+                (
+                    entity,
+                    transform
+                        .map(|transform| transform.position.xy())
+                        .unwrap_or_default(),
+                    effect.copied().unwrap_or_default(),
+                    on_death.cloned().unwrap_or_default(),
+                )
+            })
             .collect::<Vec<_>>();
 
-        for entity in to_destroy {
+        for (entity, position, effect, on_death) in to_destroy {
+            for behaviour in on_death.behaviours {
+                match behaviour {
+                    DeathBehaviour::Split { kind, count } => {
+                        let Some(kind) = enemy_database.kind(&kind) else {
+                            continue;
+                        };
+                        let behaviour = enemy_database.behaviour(kind);
+                        let angle = thread_rng().gen_range(0.0..std::f32::consts::TAU);
+                        for index in 0..count {
+                            let angle = angle + std::f32::consts::TAU * index as f32 / count as f32;
+                            let offset = Vec2::new(angle.cos(), angle.sin()) * kind.collider_radius;
+                            kind.spawn(world, position + offset, effect, behaviour.clone());
+                        }
+                    }
+                    DeathBehaviour::Explode { size, damage } => {
+                        NewGameplay::cast_spell(
+                            world,
+                            audio,
+                            PlayerCastAction {
+                                direction: Vec2::unit_x(),
+                                position,
+                                spell: Spell {
+                                    size,
+                                    effect: effect.to_effect_tag(),
+                                    shape: SpellTagShape::Point,
+                                    direction: SpellTagDirection::Down,
+                                    duration: SpellTagDuration::Instant,
+                                    damage,
+                                    ..Spell::basic()
+                                },
+                            },
+                            entity,
+                        );
+                    }
+                    DeathBehaviour::Puddle { size, duration } => {
+                        let effect = effect.to_effect_tag();
+                        if effect == SpellTagEffect::None {
+                            continue;
+                        }
+                        world.spawn((
+                            Transform::<f32, f32, f32> {
+                                position: position.into(),
+                                ..Default::default()
+                            },
+                            Zone::new(effect, size.radius(), 0.0, duration),
+                            ParticleGenerator {
+                                emmission_accumulator: 0.0,
+                                emmission_time: 0.2,
+                                texture: effect.texture().into(),
+                                batch_size: 8,
+                                spread: Vec2::new(size.radius(), 0.0),
+                            },
+                        ));
+                    }
+                    DeathBehaviour::Loot { chance, health } => {
+                        if thread_rng().gen_range(0.0..1.0) >= chance {
+                            continue;
+                        }
+                        world.spawn((
+                            Pickup {
+                                health,
+                                time_left: 15.0,
+                            },
+                            Transform::<f32, f32, f32> {
+                                position: position.into(),
+                                ..Default::default()
+                            },
+                            Collidable {
+                                space_object: Some(SpaceObject {
+                                    entity: None,
+                                    position,
+                                    collider: SpaceCollider::circle(24.0),
+                                }),
+                            },
+                            SpriteData {
+                                texture: "pickup/health".into(),
+                                ..Default::default()
+                            },
+                        ));
+                    }
+                }
+            }
+
             let _ = world.despawn(entity);
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::components::{damage::DamageLayer, enemy::Enemy};
+
+    #[test]
+    fn test_slimes_split_on_death() {
+        let database = EnemyDatabase::load();
+        let mut world = World::new();
+        let mut audio = Audio::default();
+        let slime = database.kind("slime").unwrap();
+        let entity = slime.spawn(
+            &mut world,
+            Vec2::new(100.0, 0.0),
+            SpellTagEffect::Water.into(),
+            database.behaviour(slime),
+        );
+        let kill_all = |world: &mut World| {
+            for (_, health) in world.query::<&mut Health>().iter() {
+                health.value = 0.0;
+            }
+        };
+        let enemies = |world: &World| {
+            world
+                .query::<(&Enemy, &Health, &Collidable, &Effect)>()
+                .iter()
+                .map(|(_, (_, health, collidable, effect))| {
+                    (
+                        health.limit,
+                        collidable.space_object.as_ref().unwrap().collider,
+                        effect.to_effect_tag(),
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // big slime splits into two medium ones of its element.
+        kill_all(&mut world);
+        Death::run(&mut world, &mut audio, &database);
+        assert!(!world.contains(entity));
+        let medium = database.kind("slime_medium").unwrap();
+        assert_eq!(
+            enemies(&world),
+            vec![
+                (
+                    medium.health,
+                    SpaceCollider::circle(medium.collider_radius),
+                    SpellTagEffect::Water
+                );
+                2
+            ]
+        );
+
+        // those split into small ones, which leave only puddles and loot behind.
+        kill_all(&mut world);
+        Death::run(&mut world, &mut audio, &database);
+        let small = database.kind("slime_small").unwrap();
+        assert_eq!(
+            enemies(&world),
+            vec![
+                (
+                    small.health,
+                    SpaceCollider::circle(small.collider_radius),
+                    SpellTagEffect::Water
+                );
+                4
+            ]
+        );
+        kill_all(&mut world);
+        Death::run(&mut world, &mut audio, &database);
+        assert!(enemies(&world).is_empty());
+        assert_eq!(world.query::<&Zone>().iter().count(), 4);
+        assert!(world.query::<&Pickup>().iter().count() <= 4);
+
+        // player dies without any death behaviours.
+        let player = world.spawn((Health {
+            value: 0.0,
+            limit: 10.0,
+            layer: DamageLayer::None,
+        },));
+        Death::run(&mut world, &mut audio, &database);
+        assert!(!world.contains(player));
+    }
+}
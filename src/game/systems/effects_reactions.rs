@@ -1,11 +1,13 @@
 use crate::game::{
     components::{
+        affix::Reflective,
         collidable::Collidable,
         effect::{Effect, EffectReaction},
         enemy::Enemy,
         health::Health,
         ignore_entity::IgnoreEntity,
         immobility::Immobility,
+        knockback::Knockback,
         particle::Particle,
         speed::Speed,
     },
@@ -17,9 +19,7 @@ use micro_games_kit::third_party::vek::{Transform, Vec2};
 pub struct EffectsReactions;
 
 impl EffectsReactions {
-    pub fn run(world: &mut World) {
-        let space = Space::read();
-        let space = space.read().unwrap();
+    pub fn run(world: &mut World, space: &Space) {
         let mut entities_to_process = Vec::<(Entity, Entity)>::new();
 
         for (entity_a, (collidable, _)) in world.query::<(&Collidable, &Effect)>().iter() {
@@ -27,7 +27,10 @@ impl EffectsReactions {
                 for object in space.collisions(space_object, true) {
                     if let Some(entity_b) = object.entity {
                         if entity_b != entity_a {
-                            if IgnoreEntity::should_be_ignored(&world, entity_a, entity_b) {
+                            if IgnoreEntity::should_be_ignored(world, entity_a, entity_b) {
+                                continue;
+                            }
+                            if Reflective::reflects(world, entity_a, entity_b) {
                                 continue;
                             }
 
@@ -41,29 +44,30 @@ impl EffectsReactions {
         for (entity_a, entity_b) in entities_to_process {
             let mut reaction = EffectReaction::None;
             let mut reaction_transform = Transform::default();
+            let mut impulses = Vec::<(Entity, Vec2<f32>)>::new();
 
             {
                 let mut query = world.query::<(
                     &mut Effect,
                     Option<&mut Health>,
                     Option<&mut Immobility>,
-                    Option<&mut Transform<f32, f32, f32>>,
+                    Option<&Transform<f32, f32, f32>>,
                     Option<&mut Speed>,
                     Option<&mut Enemy>,
                 )>();
                 let mut view = query.view();
                 let [entity_a_query, entity_b_query] = view.get_mut_n([entity_a, entity_b]);
 
-                if let Some((effect_a, mut health_a, immobility_a, mut transform_a, ..)) =
+                if let Some((effect_a, mut health_a, immobility_a, transform_a, ..)) =
                     entity_a_query
                 {
-                    if let Some((effect_b, mut health_b, immobility_b, mut transform_b, ..)) =
+                    if let Some((effect_b, mut health_b, immobility_b, transform_b, ..)) =
                         entity_b_query
                     {
                         reaction = effect_a.react(effect_b);
                         let damage = reaction.damage();
                         let immobile_time = reaction.immobile_time();
-                        let push_distance = reaction.push_distance();
+                        let push_impulse = reaction.push_impulse();
                         if let Some(health) = health_a.as_mut() {
                             health.value = (health.value - damage).max(0.0);
                         }
@@ -80,17 +84,15 @@ impl EffectsReactions {
                                 immobility.time_left = immobile_time;
                             }
                         }
-                        if let (Some(transform_a), Some(transform_b)) =
-                            (transform_a.as_mut(), transform_b.as_mut())
-                        {
+                        if let (Some(transform_a), Some(transform_b)) = (transform_a, transform_b) {
                             let direction_ab = (transform_b.position.xy()
                                 - transform_a.position.xy())
                             .try_normalized()
                             .unwrap_or_default();
-                            transform_a.position -= direction_ab * push_distance;
-                            transform_b.position += direction_ab * push_distance;
+                            impulses.push((entity_a, -direction_ab * push_impulse));
+                            impulses.push((entity_b, direction_ab * push_impulse));
 
-                            reaction_transform = transform_a.clone();
+                            reaction_transform = *transform_a;
                         }
                         // if let Some(speed) = speed_a.as_mut() {
                         //     if let Some(enemy) = enemy_a.as_mut() {
@@ -114,6 +116,10 @@ impl EffectsReactions {
                 }
             }
 
+            for (entity, impulse) in impulses {
+                Knockback::push(world, entity, impulse);
+            }
+
             if reaction != EffectReaction::None {
                 for _ in 0..50 {
                     match reaction {
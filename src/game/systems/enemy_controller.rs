@@ -1,13 +1,25 @@
 use super::player_controller::PlayerCastAction;
 use crate::game::{
     components::{
-        effect::Effect, enemy::Enemy, immobility::Immobility, player::Player, speed::Speed,
+        affix::{Hasted, Teleporting, Unstable},
+        collidable::Collidable,
+        effect::Effect,
+        enemy::Enemy,
+        enemy_ai::{EnemyAi, EnemySenses, EnemyState},
+        flocking::Flocking,
+        health::Health,
+        immobility::Immobility,
+        incantation::Incantation,
+        player::Player,
+        speed::Speed,
         spell::Spell,
+        spell_loadout::SpellLoadout,
     },
     states::new_gameplay::NewGameplay,
-    utils::magic::spell_tag::{
-        SpellTagDamage, SpellTagDirection, SpellTagDuration, SpellTagEffect, SpellTagShape,
-        SpellTagSize, SpellTagSpeed, SpellTagTrajectory,
+    systems::{enemy_spawn::EnemySpawn, navigation_controller::NavigationController},
+    utils::{
+        audio::Audio, magic::database::WordToSpellTagDatabase, navigation::NavigationGrid,
+        space::Space,
     },
 };
 use hecs::{Entity, World};
@@ -15,13 +27,26 @@ use micro_games_kit::third_party::{
     rand::{thread_rng, Rng},
     vek::{Transform, Vec2},
 };
+use std::collections::HashMap;
 
 pub struct EnemyController;
 
 impl EnemyController {
-    pub fn run(world: &mut World, delta_time: f32) {
+    pub fn run(
+        world: &mut World,
+        space: &Space,
+        audio: &mut Audio,
+        delta_time: f32,
+        word_to_spell_tag_database: &WordToSpellTagDatabase,
+        navigation: &NavigationController,
+        enemy_spawn: &EnemySpawn,
+    ) {
         let mut rng = thread_rng();
         let mut cast_spells = Vec::<(Entity, PlayerCastAction)>::new();
+        let mut started_incantations = Vec::<(Entity, Incantation)>::new();
+        let mut finished_incantations = Vec::<Entity>::new();
+        let steering = Self::flocking_steering(world, space);
+        let default_loadout = SpellLoadout::default();
 
         if let Some(player_position) = world
             .query::<&Transform<f32, f32, f32>>()
@@ -30,85 +55,674 @@ impl EnemyController {
             .next()
             .map(|(_, transform)| transform.position.xy())
         {
-            for (entity, (enemy, speed, transform, immobility, effect)) in world
+            Self::affixes(world, space, enemy_spawn, player_position, delta_time);
+            let paths = Self::path_directions(world, space, navigation, player_position);
+
+            for (
+                entity,
+                (
+                    enemy,
+                    ai,
+                    speed,
+                    transform,
+                    health,
+                    immobility,
+                    effect,
+                    flocking,
+                    loadout,
+                    incantation,
+                    hasted,
+                ),
+            ) in world
                 .query::<(
                     &mut Enemy,
+                    &mut EnemyAi,
                     &mut Speed,
                     &mut Transform<f32, f32, f32>,
+                    Option<&Health>,
                     Option<&Immobility>,
                     Option<&Effect>,
+                    Option<&Flocking>,
+                    Option<&SpellLoadout>,
+                    Option<&mut Incantation>,
+                    Option<&Hasted>,
                 )>()
                 .iter()
             {
-                let to_player_direction = (player_position - transform.position.xy())
-                    .try_normalized()
+                let to_player = player_position - transform.position.xy();
+                let to_player_direction = to_player.try_normalized().unwrap_or_default();
+
+                enemy.shoot_cooldown -= delta_time;
+
+                let senses = EnemySenses {
+                    distance_to_player: to_player.magnitude(),
+                    health: health
+                        .map(|health| health.value / health.limit.max(f32::EPSILON))
+                        .unwrap_or(1.0),
+                    effect: effect.copied().unwrap_or_default(),
+                    time_in_state: 0.0,
+                    cast_ready: enemy.shoot_cooldown <= 0.0,
+                };
+                let immobile = immobility
+                    .map(|immobility| immobility.time_left > 0.0)
                     .unwrap_or_default();
+                // enemy is committed to its incantation until it is spoken.
+                let speaking = if let Some(incantation) = incantation {
+                    incantation.time_left -= delta_time;
+                    if immobile {
+                        // being stunned breaks the incantation.
+                        finished_incantations.push(entity);
+                    } else if incantation.time_left <= 0.0 {
+                        cast_spells.push((
+                            entity,
+                            PlayerCastAction {
+                                direction: to_player_direction,
+                                position: transform.position.into(),
+                                spell: incantation.spell.clone(),
+                            },
+                        ));
+                        finished_incantations.push(entity);
+                    }
+                    true
+                } else {
+                    false
+                };
 
-                enemy.direction += to_player_direction.rotated_z(enemy.direction_rotation);
-                enemy.direction.normalize();
+                if !speaking && ai.think(&senses, delta_time) {
+                    match ai.state {
+                        EnemyState::Wander => {
+                            ai.wander_angle =
+                                rng.gen_range(-std::f32::consts::PI..std::f32::consts::PI);
+                        }
+                        EnemyState::Strafe => {
+                            ai.strafe_side = if rng.gen_bool(0.5) { 1.0 } else { -1.0 };
+                        }
+                        EnemyState::Cast => {
+                            enemy.direction_rotation = rng.gen_range(
+                                -std::f32::consts::FRAC_PI_2..=std::f32::consts::FRAC_PI_2,
+                            );
 
-                let mut velocity = enemy.direction * speed.value * delta_time;
+                            speed.value = 0.0;
+
+                            let loadout = loadout.unwrap_or(&default_loadout);
+                            enemy.shoot_cooldown = loadout.next_cooldown();
 
-                if let Some(immobility) = immobility {
-                    if immobility.time_left > 0.0 {
-                        velocity = Vec2::<f32>::zero();
+                            let effect = senses.effect.to_effect_tag();
+                            let text = word_to_spell_tag_database.compose(
+                                effect,
+                                &loadout.words,
+                                loadout.next_word_count(),
+                            );
+                            let spell = word_to_spell_tag_database
+                                .parse_with_effect(&text, Some(effect))
+                                .unwrap_or_else(Spell::basic);
+                            started_incantations.push((
+                                entity,
+                                Incantation {
+                                    text,
+                                    spell,
+                                    time_left: loadout.wind_up,
+                                },
+                            ));
+                        }
+                        _ => {}
                     }
                 }
 
+                let desired_direction = match ai.state {
+                    EnemyState::Wander => {
+                        ai.wander_angle += rng.gen_range(-1.0..=1.0) * delta_time * 2.0;
+                        Vec2::new(ai.wander_angle.cos(), ai.wander_angle.sin())
+                    }
+                    EnemyState::Chase => match paths.get(&entity) {
+                        Some(direction) => *direction,
+                        None => to_player_direction.rotated_z(enemy.direction_rotation),
+                    },
+                    EnemyState::Strafe => {
+                        Vec2::new(-to_player_direction.y, to_player_direction.x) * ai.strafe_side
+                    }
+                    EnemyState::Retreat => -to_player_direction,
+                    EnemyState::Charge => {
+                        paths.get(&entity).copied().unwrap_or(to_player_direction)
+                    }
+                    EnemyState::Cast => Vec2::zero(),
+                };
+                let chase = flocking.map(|flocking| flocking.chase).unwrap_or(1.0);
+
+                enemy.direction +=
+                    desired_direction * chase + steering.get(&entity).copied().unwrap_or_default();
+                enemy.direction = enemy.direction.try_normalized().unwrap_or_default();
+
+                let mut velocity = enemy.direction * speed.value * delta_time;
+
+                if immobile {
+                    velocity = Vec2::<f32>::zero();
+                }
+
                 if velocity.x >= 0.0 {
                     transform.scale.x = transform.scale.x.abs();
                 } else {
                     transform.scale.x = -transform.scale.x.abs();
                 }
                 transform.position += velocity;
-                speed.value =
-                    (speed.value + enemy.acceleration * delta_time).min(enemy.speed_limit);
+                let speed_factor =
+                    ai.state.speed_factor() * hasted.map(|hasted| hasted.multiplier).unwrap_or(1.0);
+                speed.value = (speed.value + enemy.acceleration * speed_factor * delta_time)
+                    .min(enemy.speed_limit * speed_factor);
+            }
+        }
 
-                enemy.shoot_cooldown -= delta_time;
+        for (entity, incantation) in started_incantations {
+            let _ = world.insert_one(entity, incantation);
+        }
 
-                if enemy.shoot_cooldown <= 0.0 {
-                    enemy.direction_rotation =
-                        rng.gen_range(-std::f32::consts::FRAC_PI_2..=std::f32::consts::FRAC_PI_2);
-
-                    speed.value = 0.0;
-
-                    enemy.shoot_cooldown = rng.gen_range(5.0..=15.0);
-
-                    cast_spells.push((
-                        entity,
-                        PlayerCastAction {
-                            direction: to_player_direction,
-                            position: transform.position.into(),
-                            spell: Spell {
-                                direction: SpellTagDirection::Forward,
-                                duration: match effect {
-                                    None => SpellTagDuration::Instant,
-                                    Some(v) => {
-                                        if v.to_effect_tag() == SpellTagEffect::None {
-                                            SpellTagDuration::Instant
-                                        } else {
-                                            SpellTagDuration::Medium
-                                        }
-                                    }
-                                },
-                                effect: match effect {
-                                    None => SpellTagEffect::None,
-                                    Some(v) => v.to_effect_tag(),
-                                },
-                                shape: SpellTagShape::Point,
-                                size: SpellTagSize::Small,
-                                speed: SpellTagSpeed::Medium,
-                                trajectory: SpellTagTrajectory::Straight,
-                                damage: SpellTagDamage::Low,
-                            },
-                        },
-                    ));
+        for entity in finished_incantations {
+            let _ = world.remove_one::<Incantation>(entity);
+        }
+
+        for spell in cast_spells {
+            NewGameplay::cast_spell(world, audio, spell.1, spell.0);
+        }
+    }
+
+    // Blinks teleporting enemies next to the player, onto free spots inside
+    // the arena, and switches elements of unstable ones, both wait while enemy
+    // is immobile.
+    fn affixes(
+        world: &World,
+        space: &Space,
+        enemy_spawn: &EnemySpawn,
+        player_position: Vec2<f32>,
+        delta_time: f32,
+    ) {
+        let immobile = |immobility: Option<&Immobility>| {
+            immobility
+                .map(|immobility| immobility.time_left > 0.0)
+                .unwrap_or_default()
+        };
+
+        let mut blinks = Vec::<(Entity, f32, f32)>::new();
+        for (entity, (teleporting, collidable, immobility)) in world
+            .query::<(&mut Teleporting, Option<&Collidable>, Option<&Immobility>)>()
+            .iter()
+        {
+            if immobile(immobility) {
+                continue;
+            }
+            teleporting.time_left -= delta_time;
+            if teleporting.time_left <= 0.0 {
+                teleporting.time_left = teleporting.interval;
+                let radius = collidable
+                    .and_then(|collidable| collidable.space_object.as_ref())
+                    .map(|object| object.collider.extents().reduce_partial_max())
+                    .unwrap_or_default();
+                blinks.push((entity, teleporting.distance, radius));
+            }
+        }
+
+        // no free spot means the enemy stays where it is until next blink.
+        for (entity, distance, radius) in blinks {
+            if let Some(position) =
+                enemy_spawn.position_around(world, space, player_position, distance, radius)
+            {
+                if let Ok(mut transform) = world.get::<&mut Transform<f32, f32, f32>>(entity) {
+                    transform.position = position.into();
                 }
             }
         }
 
-        for spell in cast_spells {
-            NewGameplay::cast_spell(world, spell.1, spell.0);
+        for (_, (unstable, effect, immobility)) in world
+            .query::<(&mut Unstable, &mut Effect, Option<&Immobility>)>()
+            .iter()
+        {
+            if immobile(immobility) {
+                continue;
+            }
+            unstable.time_left -= delta_time;
+            if unstable.time_left <= 0.0 {
+                unstable.time_left = unstable.interval;
+                *effect = Unstable::next_effect(effect.to_effect_tag()).into();
+            }
+        }
+    }
+
+    // Enemies that cannot reach the player in straight line because of
+    // obstacles or walls follow navigation flow field instead.
+    fn path_directions(
+        world: &World,
+        space: &Space,
+        navigation: &NavigationController,
+        player_position: Vec2<f32>,
+    ) -> HashMap<Entity, Vec2<f32>> {
+        world
+            .query::<(&Transform<f32, f32, f32>, Option<&Collidable>)>()
+            .with::<&Enemy>()
+            .iter()
+            .filter_map(|(entity, (transform, collidable))| {
+                let position = transform.position.xy();
+                let radius = collidable
+                    .and_then(|collidable| collidable.space_object.as_ref())
+                    .map(|object| object.collider.extents().x)
+                    .unwrap_or_default();
+                let blocked = space
+                    .sweep(position, player_position, radius)
+                    .filter_map(|object| object.entity)
+                    .any(|other| {
+                        other != entity
+                            && NavigationGrid::is_blocker(world, other, navigation.wall_lifetime)
+                    });
+                if !blocked {
+                    return None;
+                }
+                Some((entity, navigation.grid.direction(position)?))
+            })
+            .collect()
+    }
+
+    // Separation, alignment and cohesion from neighbouring enemies, already
+    // multiplied by their flocking weights.
+    fn flocking_steering(world: &World, space: &Space) -> HashMap<Entity, Vec2<f32>> {
+        let enemies = world
+            .query::<(&Enemy, &Transform<f32, f32, f32>)>()
+            .iter()
+            .map(|(entity, (enemy, transform))| {
+                (entity, (transform.position.xy(), enemy.direction))
+            })
+            .collect::<HashMap<_, _>>();
+
+        world
+            .query::<(&Flocking, &Transform<f32, f32, f32>)>()
+            .with::<&Enemy>()
+            .iter()
+            .map(|(entity, (flocking, transform))| {
+                let position = transform.position.xy();
+                let mut separation = Vec2::<f32>::zero();
+                let mut alignment = Vec2::<f32>::zero();
+                let mut center = Vec2::<f32>::zero();
+                let mut count = 0;
+
+                for object in space.nearest_in_range(position, flocking.neighbour_range) {
+                    let Some(other) = object.entity.filter(|other| *other != entity) else {
+                        continue;
+                    };
+                    let Some((other_position, other_direction)) = enemies.get(&other) else {
+                        continue;
+                    };
+                    let difference = position - *other_position;
+                    let distance = difference.magnitude();
+                    let away = if distance > f32::EPSILON {
+                        difference / distance
+                    } else {
+                        // stacked on the same spot - break the tie by entity order.
+                        let angle = entity.id().min(other.id()) as f32 * 2.4;
+                        let sign = if entity.id() < other.id() { 1.0 } else { -1.0 };
+                        Vec2::new(angle.cos(), angle.sin()) * sign
+                    };
+                    separation += away * (1.0 - distance / flocking.neighbour_range).max(0.0);
+                    alignment += *other_direction;
+                    center += *other_position;
+                    count += 1;
+                }
+
+                if count == 0 {
+                    return (entity, Vec2::zero());
+                }
+                let cohesion = (center / count as f32 - position)
+                    .try_normalized()
+                    .unwrap_or_default();
+                let alignment = alignment.try_normalized().unwrap_or_default();
+                (
+                    entity,
+                    separation * flocking.separation
+                        + alignment * flocking.alignment
+                        + cohesion * flocking.cohesion,
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{
+        components::{
+            damage::DamageLayer, enemy_ai::EnemyBehaviour, obstacle::Obstacle,
+            projectile::Projectile,
+        },
+        systems::{collision_detector::CollisionDetector, obstacle_collision::ObstacleCollision},
+        utils::{
+            enemies::EnemyDatabase,
+            magic::spell_tag::{SpellTag, SpellTagEffect, SpellTagSize},
+            navigation::NavigationData,
+            space::SpaceCollider,
+            test_utils::{collidable, player},
+        },
+    };
+    use micro_games_kit::third_party::{toml, vek::Vec3};
+
+    fn spawn_enemy(world: &mut World, flocking: Option<Flocking>) -> Entity {
+        let entity = world.spawn((
+            Enemy::default(),
+            EnemyAi::default(),
+            Speed { value: 50.0 },
+            Transform::<f32, f32, f32>::default(),
+            collidable(SpaceCollider::circle(30.0)),
+        ));
+        if let Some(flocking) = flocking {
+            let _ = world.insert_one(entity, flocking);
+        }
+        entity
+    }
+
+    fn simulate(flocking: Option<Flocking>) -> f32 {
+        let mut world = World::new();
+        let mut space = Space::default();
+        let mut audio = Audio::default();
+        world.spawn((
+            player(),
+            Transform::<f32, f32, f32> {
+                position: Vec2::new(1000.0, 0.0).into(),
+                ..Default::default()
+            },
+        ));
+        let a = spawn_enemy(&mut world, flocking);
+        let b = spawn_enemy(&mut world, flocking);
+
+        for _ in 0..120 {
+            CollisionDetector::run(&world, &mut space);
+            EnemyController::run(
+                &mut world,
+                &space,
+                &mut audio,
+                1.0 / 60.0,
+                &Default::default(),
+                &Default::default(),
+                &EnemySpawn::new(0.0, 0.0, 0),
+            );
         }
+
+        let a = world.get::<&Transform<f32, f32, f32>>(a).unwrap().position;
+        let b = world.get::<&Transform<f32, f32, f32>>(b).unwrap().position;
+        a.distance(b)
+    }
+
+    #[test]
+    fn test_enemies_separate() {
+        assert!(simulate(None) < 1.0e-3);
+        assert!(simulate(Some(Flocking::default())) > 60.0);
+        assert!(simulate(Some(EnemyDatabase::load().kind("truck").unwrap().flocking)) > 60.0);
+    }
+
+    #[test]
+    fn test_enemy_states() {
+        let behaviour = toml::from_str::<EnemyBehaviour>(
+            r#"
+            initial = "wander"
+            transitions = [
+                { to = "cast", when = { cast_ready = true } },
+                { from = "cast", to = "chase", when = { time_in_state_above = 0.2 } },
+                { to = "retreat", when = { health_below = 0.5 } },
+                { from = "retreat", to = "wander", when = { distance_above = 800.0 } },
+                { from = "wander", to = "chase", when = { distance_below = 500.0 } },
+                { from = "chase", to = "charge", when = { distance_below = 200.0 } },
+                { from = "charge", to = "strafe", when = { time_in_state_above = 0.5 } },
+                { from = "strafe", to = "chase", when = { element = "water" } },
+            ]
+            "#,
+        )
+        .unwrap();
+        let mut world = World::new();
+        let mut space = Space::default();
+        let mut audio = Audio::default();
+        let database = WordToSpellTagDatabase::default()
+            .with("small", SpellTag::Size(SpellTagSize::Small))
+            .with("wet", SpellTag::Effect(SpellTagEffect::Water));
+        let player = world.spawn((
+            player(),
+            Transform::<f32, f32, f32> {
+                position: Vec2::new(1000.0, 0.0).into(),
+                ..Default::default()
+            },
+        ));
+        let enemy = world.spawn((
+            Enemy {
+                shoot_cooldown: 100.0,
+                ..Default::default()
+            },
+            EnemyAi::new(behaviour),
+            Speed { value: 50.0 },
+            Transform::<f32, f32, f32>::default(),
+            Health {
+                value: 10.0,
+                limit: 10.0,
+                layer: DamageLayer::Enemy,
+            },
+            Effect::default(),
+        ));
+
+        let mut step = |world: &mut World, seconds: f32| {
+            for _ in 0..(seconds * 60.0) as usize {
+                CollisionDetector::run(world, &mut space);
+                EnemyController::run(
+                    world,
+                    &space,
+                    &mut audio,
+                    1.0 / 60.0,
+                    &database,
+                    &Default::default(),
+                    &EnemySpawn::new(0.0, 0.0, 0),
+                );
+            }
+            world.get::<&EnemyAi>(enemy).unwrap().state
+        };
+        let place_player = |world: &mut World, distance: f32| {
+            let position = world
+                .get::<&Transform<f32, f32, f32>>(enemy)
+                .unwrap()
+                .position;
+            world
+                .get::<&mut Transform<f32, f32, f32>>(player)
+                .unwrap()
+                .position = position + Vec3::new(distance, 0.0, 0.0);
+        };
+        let distance = |world: &World| {
+            let a = world
+                .get::<&Transform<f32, f32, f32>>(enemy)
+                .unwrap()
+                .position;
+            let b = world
+                .get::<&Transform<f32, f32, f32>>(player)
+                .unwrap()
+                .position;
+            a.distance(b)
+        };
+
+        // far away player is ignored.
+        assert_eq!(step(&mut world, 0.5), EnemyState::Wander);
+
+        // coming closer makes it chase.
+        place_player(&mut world, 400.0);
+        assert_eq!(step(&mut world, 0.1), EnemyState::Chase);
+        let before = distance(&world);
+        step(&mut world, 0.5);
+        assert!(distance(&world) < before);
+
+        // being close makes it charge, for a while only.
+        place_player(&mut world, 150.0);
+        assert_eq!(step(&mut world, 0.1), EnemyState::Charge);
+        assert_eq!(step(&mut world, 0.5), EnemyState::Strafe);
+
+        // elemental status breaks strafing.
+        *world.get::<&mut Effect>(enemy).unwrap() = SpellTagEffect::Water.into();
+        assert_eq!(step(&mut world, 1.0 / 60.0), EnemyState::Chase);
+
+        // low health makes it run away until it is far enough.
+        world.get::<&mut Health>(enemy).unwrap().value = 4.0;
+        place_player(&mut world, 300.0);
+        assert_eq!(step(&mut world, 0.1), EnemyState::Retreat);
+        let before = distance(&world);
+        step(&mut world, 0.5);
+        assert!(distance(&world) > before);
+        place_player(&mut world, 900.0);
+        assert_eq!(step(&mut world, 1.0 / 60.0), EnemyState::Wander);
+
+        // ready spell is spoken first, cast after wind-up and enemy goes back to chasing.
+        world.get::<&mut Health>(enemy).unwrap().value = 10.0;
+        place_player(&mut world, 400.0);
+        world.get::<&mut Enemy>(enemy).unwrap().shoot_cooldown = 0.0;
+        assert_eq!(step(&mut world, 1.0 / 60.0), EnemyState::Cast);
+        assert_eq!(
+            world.get::<&Incantation>(enemy).unwrap().text,
+            "wet small".to_owned()
+        );
+        assert!(world.get::<&Enemy>(enemy).unwrap().shoot_cooldown > 0.0);
+        assert_eq!(step(&mut world, 0.5), EnemyState::Cast);
+        assert_eq!(world.query::<&Projectile>().iter().count(), 0);
+        step(&mut world, 0.6);
+        assert!(world.get::<&Incantation>(enemy).is_err());
+        assert_eq!(
+            world
+                .query::<&Spell>()
+                .iter()
+                .map(|(_, spell)| (spell.effect, spell.size))
+                .collect::<Vec<_>>(),
+            vec![(SpellTagEffect::Water, SpellTagSize::Small)]
+        );
+        assert_eq!(step(&mut world, 0.5), EnemyState::Chase);
+
+        // stunning the enemy interrupts its incantation.
+        world.get::<&mut Enemy>(enemy).unwrap().shoot_cooldown = 0.0;
+        assert_eq!(step(&mut world, 1.0 / 60.0), EnemyState::Cast);
+        assert!(world.get::<&Incantation>(enemy).is_ok());
+        let _ = world.insert_one(enemy, Immobility { time_left: 0.5 });
+        step(&mut world, 1.0 / 60.0);
+        assert!(world.get::<&Incantation>(enemy).is_err());
+        assert_eq!(world.query::<&Projectile>().iter().count(), 1);
+    }
+
+    #[test]
+    fn test_teleporting_and_unstable() {
+        let mut world = World::new();
+        let mut space = Space::default();
+        let mut enemy_spawn = EnemySpawn::new(0.0, 0.0, 0);
+        enemy_spawn.arena_half_extent = 1050.0;
+        let enemy = world.spawn((
+            Transform::<f32, f32, f32>::default(),
+            collidable(SpaceCollider::circle(30.0)),
+            Effect::default(),
+            Teleporting {
+                interval: 1.0,
+                distance: 100.0,
+                time_left: 0.5,
+            },
+            Unstable {
+                interval: 1.0,
+                time_left: 0.5,
+            },
+            Immobility { time_left: 0.0 },
+        ));
+        let player_position = Vec2::new(1000.0, 0.0);
+        let effect = |world: &World| world.get::<&Effect>(enemy).unwrap().to_effect_tag();
+        let position = |world: &World| {
+            world
+                .get::<&Transform<f32, f32, f32>>(enemy)
+                .unwrap()
+                .position
+                .xy()
+        };
+
+        EnemyController::affixes(&world, &space, &enemy_spawn, player_position, 0.4);
+        assert_eq!(effect(&world), SpellTagEffect::None);
+        EnemyController::affixes(&world, &space, &enemy_spawn, player_position, 0.2);
+        assert_eq!(effect(&world), SpellTagEffect::Fire);
+
+        // blinks next to the player, but never out of the arena.
+        let blinked = position(&world);
+        assert!(blinked.distance(player_position) <= 100.0 + 1.0e-3);
+        assert!(blinked.x <= 1020.0 && blinked.y.abs() <= 1020.0);
+
+        // stunned enemy keeps its element.
+        world.get::<&mut Immobility>(enemy).unwrap().time_left = 5.0;
+        EnemyController::affixes(&world, &space, &enemy_spawn, player_position, 2.0);
+        assert_eq!(effect(&world), SpellTagEffect::Fire);
+        world.get::<&mut Immobility>(enemy).unwrap().time_left = 0.0;
+        EnemyController::affixes(&world, &space, &enemy_spawn, player_position, 1.0);
+        assert_eq!(effect(&world), SpellTagEffect::Water);
+
+        // does not land inside obstacles, stays put when there is no room.
+        world.spawn((
+            Obstacle::default(),
+            Transform::<f32, f32, f32> {
+                position: player_position.into(),
+                ..Default::default()
+            },
+            collidable(SpaceCollider::circle(200.0)),
+        ));
+        CollisionDetector::run(&world, &mut space);
+        let stuck = position(&world);
+        EnemyController::affixes(&world, &space, &enemy_spawn, player_position, 1.0);
+        assert_eq!(position(&world), stuck);
+    }
+
+    #[test]
+    fn test_enemies_walk_around_walls() {
+        let simulate = |use_navigation: bool| {
+            let mut world = World::new();
+            let mut space = Space::default();
+            let mut audio = Audio::default();
+            let database = WordToSpellTagDatabase::default();
+            let mut navigation = NavigationController::new(NavigationData {
+                half_extent: 1000.0,
+                cell_size: 50.0,
+                wall_lifetime: 1.0,
+            });
+            world.spawn((
+                player(),
+                Transform::<f32, f32, f32> {
+                    position: Vec2::new(300.0, 0.0).into(),
+                    ..Default::default()
+                },
+            ));
+            world.spawn((
+                Obstacle::default(),
+                Transform::<f32, f32, f32>::default(),
+                collidable(SpaceCollider::oriented_box(Vec2::new(20.0, 300.0), 0.0)),
+            ));
+            let enemy = spawn_enemy(&mut world, None);
+            world
+                .get::<&mut Transform<f32, f32, f32>>(enemy)
+                .unwrap()
+                .position = Vec3::new(-300.0, 0.0, 0.0);
+
+            for _ in 0..600 {
+                CollisionDetector::run(&world, &mut space);
+                if use_navigation {
+                    navigation.run(&world, 1.0 / 60.0);
+                }
+                EnemyController::run(
+                    &mut world,
+                    &space,
+                    &mut audio,
+                    1.0 / 60.0,
+                    &database,
+                    &navigation,
+                    &EnemySpawn::new(0.0, 0.0, 0),
+                );
+                ObstacleCollision::run(&world, &space);
+            }
+
+            let x = world
+                .get::<&Transform<f32, f32, f32>>(enemy)
+                .unwrap()
+                .position
+                .x;
+            x
+        };
+
+        // walking straight it gets stuck at the wall, following the field
+        // it gets to the player side.
+        assert!(simulate(false) < 0.0);
+        assert!(simulate(true) > 100.0);
     }
 }
@@ -1,31 +1,44 @@
 use crate::game::{
     components::{
-        animation::Animation,
-        collidable::Collidable,
-        damage::{Damage, DamageLayer},
-        effect::Effect,
-        enemy::Enemy,
-        health::Health,
-        immobility::Immobility,
-        player::Player,
-        speed::Speed,
-        sprite_data::SpriteData,
+        effect::Effect, enemy::Enemy, obstacle::Obstacle, player::Player, projectile::Projectile,
+        zone::Zone,
     },
-    utils::space::SpaceObject,
-};
-use hecs::World;
-use micro_games_kit::{
-    animation::{FrameAnimation, NamedAnimation},
-    third_party::{
-        rand::{thread_rng, Rng},
-        vek::{Transform, Vec2},
+    utils::{
+        enemies::EnemyDatabase,
+        magic::spell_tag::SpellTagEffect,
+        map::SpawnZoneData,
+        space::{Space, SpaceCollider, SpaceObject},
     },
 };
+use hecs::{Entity, World};
+use micro_games_kit::third_party::{
+    rand::{distributions::WeightedIndex, prelude::Distribution, thread_rng, Rng},
+    vek::{Transform, Vec2},
+};
+
+const SPAWN_ATTEMPTS: usize = 16;
+/// Spawns closer to the player than this fraction of `distance_from_player` are unfair.
+const MIN_DISTANCE_FACTOR: f32 = 0.5;
+/// Spawn zones further than this multiple of `distance_from_player` are out of reach.
+const MAX_ZONE_DISTANCE_FACTOR: f32 = 2.0;
 
 pub struct EnemySpawn {
     pub distance_from_player: f32,
+    /// Half of the size of square arena enemies have to stay in.
+    pub arena_half_extent: f32,
+    /// Designer placed spawn areas, used instead of spawning around the player
+    /// when some is in reach.
+    pub zones: Vec<SpawnZoneData>,
     pub interval_seconds: f32,
+    /// Maximum number of enemies alive at once.
     pub count_limit: usize,
+    /// Maximum number of enemies spawned in total, `None` means unlimited.
+    pub spawn_limit: Option<usize>,
+    /// Enemy kinds with their spawn weights, empty means weights from database.
+    pub kinds: Vec<(String, u32)>,
+    /// Enemy elements with their spawn weights.
+    pub effects: Vec<(SpellTagEffect, u32)>,
+    pub spawned: usize,
     time_accumulator: f32,
 }
 
@@ -33,92 +46,280 @@ impl EnemySpawn {
     pub fn new(distance_from_player: f32, interval_seconds: f32, count_limit: usize) -> Self {
         Self {
             distance_from_player,
+            arena_half_extent: f32::INFINITY,
+            zones: Default::default(),
             interval_seconds,
             count_limit,
+            spawn_limit: None,
+            kinds: Default::default(),
+            effects: vec![
+                (SpellTagEffect::None, 1),
+                (SpellTagEffect::Fire, 1),
+                (SpellTagEffect::Water, 1),
+                (SpellTagEffect::Electric, 1),
+            ],
+            spawned: 0,
             time_accumulator: 0.0,
         }
     }
 
-    pub fn run(&mut self, world: &mut World, delta_time: f32) {
+    pub fn run(
+        &mut self,
+        world: &mut World,
+        space: &Space,
+        database: &EnemyDatabase,
+        alive_time_seconds: f32,
+        delta_time: f32,
+    ) {
         let count = world.query::<&Enemy>().iter().count();
         if count >= self.count_limit {
             return;
         }
+        if self
+            .spawn_limit
+            .map(|limit| self.spawned >= limit)
+            .unwrap_or_default()
+        {
+            return;
+        }
+
+        if self.player_position(world).is_none() {
+            return;
+        }
+        self.time_accumulator -= delta_time;
+        if self.time_accumulator > 0.0 {
+            return;
+        }
+        self.time_accumulator = self.interval_seconds;
+        let kinds = if self.kinds.is_empty() {
+            database.weights()
+        } else {
+            self.kinds.clone()
+        };
+        if let Some(kind) = Self::pick(&kinds) {
+            if let Some(entity) = self.spawn_kind(world, space, database, &kind) {
+                let affixes = database.elites.roll(alive_time_seconds);
+                database.elites.apply(world, entity, affixes);
+                self.spawned += 1;
+            }
+        }
+    }
+
+    /// Spawns enemy of given kind around the player, outside of regular spawn
+    /// limits. Returns `None` when there is no free spot for it right now.
+    pub fn spawn_kind(
+        &self,
+        world: &mut World,
+        space: &Space,
+        database: &EnemyDatabase,
+        kind: &str,
+    ) -> Option<Entity> {
+        let player_position = self.player_position(world)?;
+        let kind = database.kind(kind)?;
+        let position = self.spawn_position(world, space, player_position, kind.collider_radius)?;
+        let effect = Self::pick(&kind.element_weights(&self.effects))
+            .map(Effect::from)
+            .unwrap_or_default();
+
+        Some(kind.spawn(world, position, effect, database.behaviour(kind)))
+    }
+
+    /// Free spot for enemy of given radius: inside the arena, not too close to
+    /// the player and not overlapping obstacles or spells.
+    pub fn spawn_position(
+        &self,
+        world: &World,
+        space: &Space,
+        player_position: Vec2<f32>,
+        radius: f32,
+    ) -> Option<Vec2<f32>> {
+        let mut rng = thread_rng();
+        let min_distance = self.distance_from_player * MIN_DISTANCE_FACTOR;
+        let max_distance = self.distance_from_player * MAX_ZONE_DISTANCE_FACTOR;
+        let zones = self
+            .zones
+            .iter()
+            .filter(|zone| {
+                let distance = Vec2::from(zone.position).distance(player_position);
+                distance >= min_distance && distance <= max_distance
+            })
+            .map(|zone| (*zone, zone.weight))
+            .collect::<Vec<_>>();
+        let limit = (self.arena_half_extent - radius).max(0.0);
+
+        for _ in 0..SPAWN_ATTEMPTS {
+            let angle = rng.gen_range(-180.0f32..180.0f32).to_radians();
+            let (y, x) = angle.sin_cos();
+            let position = match Self::pick(&zones) {
+                Some(zone) => {
+                    Vec2::from(zone.position)
+                        + Vec2 { x, y } * zone.radius * rng.gen_range(0.0f32..1.0).sqrt()
+                }
+                None => player_position + Vec2 { x, y } * self.distance_from_player,
+            };
+            // clamping near the arena edge can pull spot right next to the player.
+            let position = position.map(|value| value.clamp(-limit, limit));
+            if position.distance(player_position) < min_distance {
+                continue;
+            }
+            if Self::is_occupied(world, space, position, radius) {
+                continue;
+            }
+            return Some(position);
+        }
+        None
+    }
 
-        let query = world
+    /// Free spot for enemy of given radius at `distance` from `center` in
+    /// random direction, inside the arena and not overlapping obstacles or
+    /// spells.
+    pub fn position_around(
+        &self,
+        world: &World,
+        space: &Space,
+        center: Vec2<f32>,
+        distance: f32,
+        radius: f32,
+    ) -> Option<Vec2<f32>> {
+        let mut rng = thread_rng();
+        let limit = (self.arena_half_extent - radius).max(0.0);
+
+        for _ in 0..SPAWN_ATTEMPTS {
+            let angle = rng.gen_range(-180.0f32..180.0f32).to_radians();
+            let (y, x) = angle.sin_cos();
+            let position =
+                (center + Vec2 { x, y } * distance).map(|value| value.clamp(-limit, limit));
+            if !Self::is_occupied(world, space, position, radius) {
+                return Some(position);
+            }
+        }
+        None
+    }
+
+    fn is_occupied(world: &World, space: &Space, position: Vec2<f32>, radius: f32) -> bool {
+        let probe = SpaceObject {
+            entity: None,
+            position,
+            collider: SpaceCollider::circle(radius),
+        };
+        let occupied = space
+            .collisions(&probe, true)
+            .filter_map(|object| object.entity)
+            .any(|entity| {
+                world.satisfies::<&Obstacle>(entity).unwrap_or_default()
+                    || world.satisfies::<&Projectile>(entity).unwrap_or_default()
+            });
+        occupied
+            || world
+                .query::<(&Zone, &Transform<f32, f32, f32>)>()
+                .iter()
+                .any(|(_, (zone, transform))| {
+                    transform.position.xy().distance(position) < zone.radius + radius
+                })
+    }
+
+    fn player_position(&self, world: &World) -> Option<Vec2<f32>> {
+        world
             .query::<&Transform<f32, f32, f32>>()
             .with::<&Player>()
             .iter()
             .next()
-            .map(|(_, transform)| transform.position.xy());
+            .map(|(_, transform)| transform.position.xy())
+    }
+
+    fn pick<T: Clone>(items: &[(T, u32)]) -> Option<T> {
+        let distribution = WeightedIndex::new(items.iter().map(|(_, weight)| *weight)).ok()?;
+        Some(items[distribution.sample(&mut thread_rng())].0.clone())
+    }
+}
 
-        if let Some(player_position) = query {
-            self.time_accumulator -= delta_time;
-            if self.time_accumulator > 0.0 {
-                return;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn blocker<T: hecs::Component>(
+        world: &mut World,
+        component: T,
+        position: Vec2<f32>,
+        radius: f32,
+    ) -> SpaceObject {
+        SpaceObject {
+            entity: Some(world.spawn((component,))),
+            position,
+            collider: SpaceCollider::circle(radius),
+        }
+    }
+
+    #[test]
+    fn test_spawn_position() {
+        let mut world = World::new();
+        let mut space = Space::default();
+        let mut spawn = EnemySpawn::new(1000.0, 1.0, 10);
+        spawn.arena_half_extent = 3500.0;
+
+        // player in the corner still gets enemies inside the arena, far enough.
+        let player = Vec2::new(3400.0, -3400.0);
+        for _ in 0..100 {
+            let position = spawn.spawn_position(&world, &space, player, 40.0).unwrap();
+            assert!(position.x.abs() <= 3460.0 && position.y.abs() <= 3460.0);
+            assert!(position.distance(player) >= 500.0, "{:?}", position);
+        }
+
+        // obstacles and active spells around the player are avoided.
+        let player = Vec2::zero();
+        let obstacle = blocker(
+            &mut world,
+            Obstacle::default(),
+            Vec2::new(1000.0, 0.0),
+            500.0,
+        );
+        let spell = blocker(
+            &mut world,
+            Projectile::new(0.0, Vec2::zero(), 5.0),
+            Vec2::new(-1000.0, 0.0),
+            500.0,
+        );
+        space.rebuild(vec![obstacle, spell]);
+        for _ in 0..100 {
+            if let Some(position) = spawn.spawn_position(&world, &space, player, 40.0) {
+                assert!(position.distance(Vec2::new(1000.0, 0.0)) >= 540.0);
+                assert!(position.distance(Vec2::new(-1000.0, 0.0)) >= 540.0);
             }
-            self.time_accumulator = self.interval_seconds;
-            let angle = thread_rng().gen_range(-180.0f32..180.0f32).to_radians();
-            let (y, x) = angle.sin_cos();
-            let position = player_position + Vec2 { x, y } * self.distance_from_player;
-            let effect = match thread_rng().gen_range(0..4) {
-                0 => Effect::default(),
-                1 => Effect {
-                    fire: true,
-                    ..Default::default()
-                },
-                2 => Effect {
-                    water: true,
-                    ..Default::default()
-                },
-                3 => Effect {
-                    electricity: true,
-                    ..Default::default()
-                },
-                _ => unreachable!(),
-            };
+        }
 
-            let kind = if thread_rng().gen_ratio(1, 3) {
-                "truck"
-            } else {
-                "slime"
-            };
-            let _ = world.spawn((
-                Enemy::default(),
-                Animation {
-                    animation: Some(NamedAnimation {
-                        animation: FrameAnimation::new(0..1).fps(10.0).looping().playing(),
-                        id: kind.to_owned(),
-                    }),
-                },
-                Transform::<f32, f32, f32> {
-                    position: position.into(),
-                    ..Default::default()
-                },
-                Collidable {
-                    space_object: Some(SpaceObject {
-                        entity: None,
-                        position,
-                        collider_radius: 30.0,
-                    }),
-                },
-                SpriteData {
-                    texture: format!("{}/0", kind).into(),
-                    ..Default::default()
-                },
-                effect,
-                Health {
-                    value: 50.0,
-                    limit: 50.0,
-                    layer: DamageLayer::Enemy,
-                },
-                Damage {
-                    value: 1.0,
-                    layer: DamageLayer::Enemy,
-                },
-                Speed::new(40.0..=100.0),
-                Immobility { time_left: 0.0 },
-            ));
+        // zones out of reach are ignored, the one in reach is used.
+        let space = Space::default();
+        spawn.zones = vec![
+            SpawnZoneData {
+                position: [100.0, 0.0],
+                radius: 50.0,
+                weight: 10,
+            },
+            SpawnZoneData {
+                position: [0.0, 3000.0],
+                radius: 50.0,
+                weight: 10,
+            },
+            SpawnZoneData {
+                position: [-1500.0, 0.0],
+                radius: 50.0,
+                weight: 1,
+            },
+        ];
+        for _ in 0..100 {
+            let position = spawn.spawn_position(&world, &space, player, 40.0).unwrap();
+            assert!(position.distance(Vec2::new(-1500.0, 0.0)) <= 50.0);
         }
+
+        // no free spot at all gives up instead of spawning unfairly.
+        let mut space = Space::default();
+        spawn.zones.clear();
+        space.rebuild(vec![blocker(
+            &mut world,
+            Obstacle::default(),
+            player,
+            2000.0,
+        )]);
+        assert_eq!(spawn.spawn_position(&world, &space, player, 40.0), None);
     }
 }
@@ -0,0 +1,76 @@
+use crate::game::components::{homing::Homing, projectile::Projectile};
+use hecs::World;
+use micro_games_kit::third_party::vek::Transform;
+
+pub struct HomingController;
+
+impl HomingController {
+    // Runs before SpellController, so velocity follows new direction this frame.
+    pub fn run(world: &World, delta_time: f32) {
+        for (_, (projectile, homing, transform)) in world
+            .query::<(&mut Projectile, &Homing, &Transform<f32, f32, f32>)>()
+            .iter()
+        {
+            let Ok(target) = world.get::<&Transform<f32, f32, f32>>(homing.target) else {
+                continue;
+            };
+            let Some(to_target) = (target.position.xy() - transform.position.xy()).try_normalized()
+            else {
+                continue;
+            };
+            let current = projectile.direction.y.atan2(projectile.direction.x);
+            let wanted = to_target.y.atan2(to_target.x);
+            let mut difference = wanted - current;
+            while difference > std::f32::consts::PI {
+                difference -= std::f32::consts::TAU;
+            }
+            while difference < -std::f32::consts::PI {
+                difference += std::f32::consts::TAU;
+            }
+            let max_turn = homing.turn_rate * delta_time;
+            let angle = current + difference.clamp(-max_turn, max_turn);
+            projectile.direction.x = angle.cos();
+            projectile.direction.y = angle.sin();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use micro_games_kit::third_party::vek::Vec2;
+
+    #[test]
+    fn test_homing_turns_towards_target() {
+        let mut world = World::new();
+        let target = world.spawn((Transform::<f32, f32, f32> {
+            position: Vec2::new(0.0, 100.0).into(),
+            ..Default::default()
+        },));
+        let projectile = world.spawn((
+            Projectile::new(100.0, Vec2::new(1.0, 0.0), 10.0),
+            Homing {
+                target,
+                turn_rate: std::f32::consts::FRAC_PI_2,
+            },
+            Transform::<f32, f32, f32>::default(),
+        ));
+
+        // turning is limited by turn rate.
+        HomingController::run(&world, 0.5);
+        let direction = world.get::<&Projectile>(projectile).unwrap().direction;
+        let expected = Vec2::new(1.0f32, 1.0).normalized();
+        assert!(direction.distance(expected) < 1.0e-4);
+
+        // but never overshoots the target.
+        HomingController::run(&world, 10.0);
+        let direction = world.get::<&Projectile>(projectile).unwrap().direction;
+        assert!(direction.distance(Vec2::new(0.0, 1.0)) < 1.0e-4);
+
+        // missing target keeps direction.
+        world.despawn(target).unwrap();
+        HomingController::run(&world, 10.0);
+        let direction = world.get::<&Projectile>(projectile).unwrap().direction;
+        assert!(direction.distance(Vec2::new(0.0, 1.0)) < 1.0e-4);
+    }
+}
@@ -1,4 +1,6 @@
 pub mod animation_controller;
+pub mod boss_controller;
+pub mod buff_controller;
 pub mod collision_detector;
 pub mod damage_dealer;
 pub mod death;
@@ -6,10 +8,22 @@ pub mod effects_reactions;
 pub mod enemy_controller;
 pub mod enemy_jump_animation;
 pub mod enemy_spawn;
+pub mod homing_controller;
 pub mod immobility_controller;
+pub mod movement;
+pub mod navigation_controller;
+pub mod obstacle_collision;
 pub mod particle_manager;
+pub mod pending_spell_controller;
+pub mod pickup_collector;
 pub mod player_controller;
 pub mod projectile_controller;
 pub mod slime_color;
+pub mod spell_clash;
 pub mod spell_controller;
 pub mod sprite_renderer;
+pub mod summon_controller;
+pub mod wall_controller;
+pub mod wave_director;
+pub mod zone_controller;
+pub mod zone_renderer;
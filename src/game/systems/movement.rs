@@ -0,0 +1,82 @@
+use crate::game::components::{knockback::Knockback, velocity::Velocity};
+use hecs::World;
+use micro_games_kit::third_party::vek::{Transform, Vec2};
+
+/// Velocity below which entity is considered to be at rest.
+const REST_SPEED: f32 = 1.0;
+
+pub struct Movement;
+
+impl Movement {
+    // Moves entities by their velocity and slows them down with friction,
+    // obstacle collision runs after so pushes cannot go through walls.
+    pub fn run(world: &World, delta_time: f32) {
+        for (_, (velocity, transform, knockback)) in world
+            .query::<(
+                &mut Velocity,
+                &mut Transform<f32, f32, f32>,
+                Option<&Knockback>,
+            )>()
+            .iter()
+        {
+            transform.position += velocity.value * delta_time;
+
+            let friction = knockback
+                .map(|knockback| knockback.friction)
+                .unwrap_or(Knockback::FRICTION);
+            velocity.value *= (-friction * delta_time).exp();
+            if velocity.value.magnitude_squared() < REST_SPEED * REST_SPEED {
+                velocity.value = Vec2::zero();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::components::immobility::Immobility;
+
+    #[test]
+    fn test_knockback() {
+        let mut world = World::new();
+        let spawn = |world: &mut World, mass: f32| {
+            world.spawn((
+                Transform::<f32, f32, f32>::default(),
+                Velocity::default(),
+                Knockback::new(mass),
+                Immobility::default(),
+            ))
+        };
+        let light = spawn(&mut world, 1.0);
+        let heavy = spawn(&mut world, 4.0);
+        let frozen = spawn(&mut world, 1.0);
+        world.get::<&mut Immobility>(frozen).unwrap().time_left = 1.0;
+
+        for entity in [light, heavy, frozen] {
+            Knockback::push(&world, entity, Vec2::new(800.0, 0.0));
+        }
+        assert!(!Knockback::push(
+            &world,
+            world.reserve_entity(),
+            Vec2::unit_x()
+        ));
+
+        for _ in 0..120 {
+            Movement::run(&world, 1.0 / 60.0);
+        }
+        let x = |entity| {
+            world
+                .get::<&Transform<f32, f32, f32>>(entity)
+                .unwrap()
+                .position
+                .x
+        };
+
+        // slides about impulse over friction and comes to rest.
+        assert!((x(light) - 100.0).abs() < 10.0, "{}", x(light));
+        assert!((x(heavy) - 25.0).abs() < 3.0, "{}", x(heavy));
+        assert_eq!(x(frozen), 0.0);
+        assert_eq!(world.get::<&Velocity>(light).unwrap().value, Vec2::zero());
+    }
+}
@@ -0,0 +1,66 @@
+use crate::game::{
+    components::{collidable::Collidable, player::Player},
+    utils::navigation::{NavigationData, NavigationGrid},
+};
+use hecs::World;
+use micro_games_kit::third_party::vek::Transform;
+
+pub struct NavigationController {
+    pub grid: NavigationGrid,
+    pub wall_lifetime: f32,
+    /// Seconds between grid updates.
+    pub interval_seconds: f32,
+    time_accumulator: f32,
+}
+
+impl Default for NavigationController {
+    fn default() -> Self {
+        Self::new(Default::default())
+    }
+}
+
+impl NavigationController {
+    pub fn new(data: NavigationData) -> Self {
+        Self {
+            grid: NavigationGrid::new(data),
+            wall_lifetime: data.wall_lifetime,
+            interval_seconds: 0.25,
+            time_accumulator: 0.0,
+        }
+    }
+
+    pub fn run(&mut self, world: &World, delta_time: f32) {
+        self.time_accumulator -= delta_time;
+        if self.time_accumulator > 0.0 {
+            return;
+        }
+        self.time_accumulator = self.interval_seconds;
+
+        let Some(player_position) = world
+            .query::<&Transform<f32, f32, f32>>()
+            .with::<&Player>()
+            .iter()
+            .next()
+            .map(|(_, transform)| transform.position.xy())
+        else {
+            return;
+        };
+
+        let blockers = world
+            .query::<(&Collidable, &Transform<f32, f32, f32>)>()
+            .iter()
+            .filter(|(entity, _)| NavigationGrid::is_blocker(world, *entity, self.wall_lifetime))
+            .filter_map(|(_, (collidable, transform))| {
+                let mut object = collidable.space_object.clone()?;
+                object.position = transform.position.xy();
+                Some(object)
+            })
+            .collect::<Vec<_>>();
+        let changed = self.grid.set_blocked(blockers);
+
+        // field only has to follow the player when moved to another cell.
+        if changed || self.grid.cell(player_position) != self.grid.target() {
+            self.grid.compute_flow(player_position);
+        }
+    }
+}
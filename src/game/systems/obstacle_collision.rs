@@ -0,0 +1,107 @@
+use crate::game::{
+    components::{collidable::Collidable, obstacle::Obstacle, projectile::Projectile, wall::Wall},
+    utils::space::Space,
+};
+use hecs::World;
+use micro_games_kit::third_party::vek::Transform;
+
+pub struct ObstacleCollision;
+
+impl ObstacleCollision {
+    // Pushes moving bodies out of obstacles, only the penetrating part of the
+    // movement is removed so bodies slide along obstacle surface. Casters
+    // walk through their own walls.
+    pub fn run(world: &World, space: &Space) {
+        for (entity, (collidable, transform)) in world
+            .query::<(&Collidable, &mut Transform<f32, f32, f32>)>()
+            .without::<&Obstacle>()
+            .without::<&Projectile>()
+            .iter()
+        {
+            if let Some(space_object) = collidable.space_object.as_ref() {
+                let mut object = space_object.clone();
+                object.position = transform.position.xy();
+
+                let obstacles = space
+                    .collisions(&object, true)
+                    .filter(|other| {
+                        other
+                            .entity
+                            .map(|other| {
+                                world.satisfies::<&Obstacle>(other).unwrap_or_default()
+                                    && !Wall::passes(world, other, entity)
+                            })
+                            .unwrap_or_default()
+                    })
+                    .cloned()
+                    .collect::<Vec<_>>();
+
+                for obstacle in obstacles {
+                    if let Some(push) = object.contact(&obstacle) {
+                        object.position += push;
+                    }
+                }
+
+                transform.position.x = object.position.x;
+                transform.position.y = object.position.y;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{
+        components::obstacle::ObstacleProjectileResponse,
+        systems::collision_detector::CollisionDetector,
+        utils::{space::SpaceCollider, test_utils::collidable},
+    };
+    use micro_games_kit::third_party::vek::Vec2;
+
+    #[test]
+    fn test_slide_along_obstacle() {
+        let mut world = World::new();
+        let mut space = Space::default();
+
+        world.spawn((
+            Obstacle {
+                projectile_response: ObstacleProjectileResponse::Stop,
+            },
+            Transform::<f32, f32, f32>::default(),
+            collidable(SpaceCollider::circle(50.0)),
+        ));
+        let body = world.spawn((
+            Transform::<f32, f32, f32> {
+                position: Vec2::new(-100.0, 10.0).into(),
+                ..Default::default()
+            },
+            collidable(SpaceCollider::circle(10.0)),
+        ));
+
+        // walk right, straight into the obstacle.
+        for _ in 0..100 {
+            world
+                .get::<&mut Transform<f32, f32, f32>>(body)
+                .unwrap()
+                .position
+                .x += 5.0;
+            ObstacleCollision::run(&world, &space);
+            CollisionDetector::run(&world, &mut space);
+
+            let position = world
+                .get::<&Transform<f32, f32, f32>>(body)
+                .unwrap()
+                .position
+                .xy();
+            assert!(position.magnitude() >= 60.0 - 1.0e-3);
+        }
+
+        // body went around the obstacle instead of getting stuck in front of it.
+        let position = world
+            .get::<&Transform<f32, f32, f32>>(body)
+            .unwrap()
+            .position;
+        assert!(position.x > 60.0);
+    }
+}
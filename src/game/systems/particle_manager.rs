@@ -2,6 +2,7 @@ use hecs::{Entity, World};
 use micro_games_kit::{
     context::GameContext,
     third_party::{
+        rand::{thread_rng, Rng},
         spitfire_draw::{
             particles::ParticleEmitter,
             sprite::SpriteTexture,
@@ -19,6 +20,7 @@ pub struct ParticleManager {}
 
 impl ParticleManager {
     pub fn process(&mut self, world: &mut World, delta_time: f32) {
+        let mut rng = thread_rng();
         let mut particles = Vec::<Particle>::new();
 
         for (_, (generator, transform, projectile, spell)) in world
@@ -48,7 +50,7 @@ impl ParticleManager {
                 for _ in 0..generator.batch_size {
                     particles.push(Particle::new(
                         generator.texture.clone(),
-                        transform.position.xy(),
+                        transform.position.xy() + generator.spread * rng.gen_range(-1.0..=1.0),
                         velocity,
                         180.0f32.to_radians(),
                         100.0..=200.0,
@@ -0,0 +1,301 @@
+use crate::game::{
+    components::{
+        damage::DamageLayer, enemy::Enemy, health::Health, pending_spell::PendingSpell,
+        projectile::Projectile,
+    },
+    states::new_gameplay::NewGameplay,
+    utils::{audio::Audio, magic::spell_tag::SpellTagTrigger},
+};
+use hecs::{Entity, World};
+use micro_games_kit::third_party::vek::{Transform, Vec2};
+
+pub struct PendingSpellController;
+
+impl PendingSpellController {
+    pub fn run(world: &mut World, audio: &mut Audio, delta_time: f32) {
+        let pending = world
+            .query::<(&mut PendingSpell, &Transform<f32, f32, f32>)>()
+            .iter()
+            .map(|(entity, (pending, transform))| {
+                pending.ttl -= delta_time;
+                (
+                    entity,
+                    pending.trigger,
+                    pending.caster,
+                    pending.attacker,
+                    pending.layer,
+                    pending.ttl,
+                    transform.position.xy(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        // fire position and target to aim at, if there is any.
+        let mut fired = Vec::<(Entity, Vec2<f32>, Option<Vec2<f32>>)>::new();
+        let mut fizzled = Vec::<Entity>::new();
+
+        for (entity, trigger, caster, attacker, layer, ttl, position) in pending {
+            match trigger {
+                SpellTagTrigger::Delay => {
+                    if ttl <= 0.0 {
+                        fired.push((entity, position, None));
+                    }
+                }
+                SpellTagTrigger::Hit => {
+                    let Some(caster_position) = Self::position(world, caster) else {
+                        fizzled.push(entity);
+                        continue;
+                    };
+                    if let Ok(mut transform) = world.get::<&mut Transform<f32, f32, f32>>(entity) {
+                        transform.position = caster_position.into();
+                    }
+                    if let Some(attacker) = attacker {
+                        fired.push((entity, caster_position, Self::source(world, attacker)));
+                    } else if ttl <= 0.0 {
+                        fizzled.push(entity);
+                    }
+                }
+                SpellTagTrigger::Trap => {
+                    // only bodies of the opposing side set traps off, player
+                    // traps wait for enemies, not for their walls.
+                    let side = if layer == DamageLayer::None {
+                        world
+                            .get::<&Health>(caster)
+                            .map(|health| health.layer)
+                            .unwrap_or_default()
+                    } else {
+                        layer
+                    };
+                    let intruder = world
+                        .query::<(&Health, &Transform<f32, f32, f32>)>()
+                        .iter()
+                        .filter(|(other, (health, _))| {
+                            *other != caster
+                                && health.layer != DamageLayer::None
+                                && health.layer != side
+                                && (side != DamageLayer::Player
+                                    || world.satisfies::<&Enemy>(*other).unwrap_or_default())
+                        })
+                        .map(|(_, (_, transform))| transform.position.xy())
+                        .find(|other| {
+                            other.distance_squared(position)
+                                < PendingSpell::TRAP_RADIUS * PendingSpell::TRAP_RADIUS
+                        });
+                    if let Some(intruder) = intruder {
+                        fired.push((entity, position, Some(intruder)));
+                    } else if ttl <= 0.0 {
+                        fizzled.push(entity);
+                    }
+                }
+            }
+        }
+
+        for entity in fizzled {
+            let _ = world.despawn(entity);
+        }
+
+        for (entity, position, target) in fired {
+            let Ok(mut pending) = world.remove_one::<PendingSpell>(entity) else {
+                continue;
+            };
+            let _ = world.despawn(entity);
+            pending.cast.position = position;
+            if let Some(direction) = target.and_then(|target| (target - position).try_normalized())
+            {
+                pending.cast.direction = direction;
+            }
+            let entities = NewGameplay::cast_spell(world, audio, pending.cast, pending.caster);
+            if pending.layer == DamageLayer::Player {
+                NewGameplay::befriend(world, &entities);
+            }
+        }
+    }
+
+    fn position(world: &World, entity: Entity) -> Option<Vec2<f32>> {
+        world
+            .get::<&Transform<f32, f32, f32>>(entity)
+            .ok()
+            .map(|transform| transform.position.xy())
+    }
+
+    // Position of who is behind the attack: caster of spell that hit, or the
+    // spell itself when caster is gone.
+    fn source(world: &World, attacker: Entity) -> Option<Vec2<f32>> {
+        let caster = world
+            .get::<&Projectile>(attacker)
+            .ok()
+            .and_then(|projectile| projectile.caster);
+        caster
+            .and_then(|caster| Self::position(world, caster))
+            .or_else(|| Self::position(world, attacker))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{
+        components::{
+            damage::Damage, enemy::Enemy, obstacle::Obstacle, projectile::Projectile, spell::Spell,
+            wall::Wall,
+        },
+        systems::{damage_dealer::DamageDealer, player_controller::PlayerCastAction},
+        utils::{
+            magic::spell_tag::{SpellTagDuration, SpellTagEffect},
+            test_utils::player,
+        },
+    };
+
+    #[test]
+    fn test_pending_spells() {
+        let mut world = World::new();
+        let mut audio = Audio::default();
+        let player = world.spawn((
+            player(),
+            Transform::<f32, f32, f32>::default(),
+            Health {
+                value: 100.0,
+                limit: 100.0,
+                layer: DamageLayer::Player,
+            },
+        ));
+        let cast = |trigger, position: Vec2<f32>| PlayerCastAction {
+            direction: Vec2::unit_x(),
+            position,
+            spell: Spell {
+                effect: SpellTagEffect::Fire,
+                trigger: Some(trigger),
+                ..Spell::basic()
+            },
+        };
+        let projectiles = |world: &World| {
+            world
+                .query::<(&Projectile, &Damage, &Transform<f32, f32, f32>)>()
+                .iter()
+                .map(|(entity, (_, damage, transform))| {
+                    (entity, damage.layer, transform.position.xy())
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // delayed spell fires once its instant duration is up, still friendly.
+        let pending = NewGameplay::cast_spell(
+            &mut world,
+            &mut audio,
+            cast(SpellTagTrigger::Delay, Vec2::zero()),
+            player,
+        );
+        NewGameplay::befriend(&world, &pending);
+        PendingSpellController::run(&mut world, &mut audio, 0.1);
+        assert!(projectiles(&world).is_empty());
+        PendingSpellController::run(&mut world, &mut audio, 0.15);
+        let fired = projectiles(&world);
+        assert_eq!(fired.len(), 1);
+        assert_eq!(fired[0].1, DamageLayer::Player);
+        assert!(world.query::<&PendingSpell>().iter().next().is_none());
+        world.despawn(fired[0].0).unwrap();
+
+        // trap waits for enemy to step in, the player does not set it off.
+        let trap = NewGameplay::cast_spell(
+            &mut world,
+            &mut audio,
+            cast(SpellTagTrigger::Trap, Vec2::new(0.0, 300.0)),
+            player,
+        );
+        NewGameplay::befriend(&world, &trap);
+        world
+            .get::<&mut Transform<f32, f32, f32>>(player)
+            .unwrap()
+            .position = Vec2::new(0.0, 300.0).into();
+        PendingSpellController::run(&mut world, &mut audio, 0.1);
+        assert!(projectiles(&world).is_empty());
+        world
+            .get::<&mut Transform<f32, f32, f32>>(player)
+            .unwrap()
+            .position = Vec2::zero().into();
+        // neither does enemy wall raised next to it.
+        let wall = world.spawn((
+            Wall {
+                layer: DamageLayer::Enemy,
+                ..Wall::new(SpellTagEffect::Fire, Entity::DANGLING, 5.0)
+            },
+            Obstacle::default(),
+            Transform::<f32, f32, f32> {
+                position: Vec2::new(-20.0, 300.0).into(),
+                ..Default::default()
+            },
+            Health {
+                value: 20.0,
+                limit: 20.0,
+                layer: DamageLayer::Enemy,
+            },
+        ));
+        PendingSpellController::run(&mut world, &mut audio, 0.1);
+        assert!(projectiles(&world).is_empty());
+        world.despawn(wall).unwrap();
+        let enemy = world.spawn((
+            Enemy::default(),
+            Transform::<f32, f32, f32> {
+                position: Vec2::new(20.0, 300.0).into(),
+                ..Default::default()
+            },
+            Health {
+                value: 100.0,
+                limit: 100.0,
+                layer: DamageLayer::Enemy,
+            },
+        ));
+        PendingSpellController::run(&mut world, &mut audio, 0.1);
+        let fired = projectiles(&world);
+        assert_eq!(fired.len(), 1);
+        assert_eq!(fired[0].2, Vec2::new(0.0, 300.0));
+        world.despawn(fired[0].0).unwrap();
+
+        // spell waiting for hit fires from caster back at whoever attacked.
+        NewGameplay::cast_spell(
+            &mut world,
+            &mut audio,
+            cast(SpellTagTrigger::Hit, Vec2::zero()),
+            player,
+        );
+        PendingSpellController::run(&mut world, &mut audio, 5.0);
+        assert!(projectiles(&world).is_empty());
+        world
+            .get::<&mut Transform<f32, f32, f32>>(enemy)
+            .unwrap()
+            .position = Vec2::new(0.0, -200.0).into();
+        DamageDealer::deal(
+            &world,
+            enemy,
+            Damage {
+                value: 1.0,
+                layer: DamageLayer::Enemy,
+            },
+            player,
+        );
+        PendingSpellController::run(&mut world, &mut audio, 0.1);
+        let fired = projectiles(&world);
+        assert_eq!(fired.len(), 1);
+        let direction = world.get::<&Projectile>(fired[0].0).unwrap().direction;
+        assert!(direction.y < 0.0 && direction.x.abs() < 1.0e-3);
+    }
+
+    #[test]
+    fn test_delay_follows_duration() {
+        let caster = World::new().reserve_entity();
+        let delay = |duration| {
+            let cast = PlayerCastAction {
+                direction: Vec2::unit_x(),
+                position: Vec2::zero(),
+                spell: Spell {
+                    duration,
+                    trigger: Some(SpellTagTrigger::Delay),
+                    ..Spell::basic()
+                },
+            };
+            PendingSpell::new(cast, caster).ok().unwrap().ttl
+        };
+        assert_eq!(delay(SpellTagDuration::Quick), 0.5);
+        assert_eq!(delay(SpellTagDuration::Long), 8.0);
+    }
+}
@@ -0,0 +1,105 @@
+use crate::game::{
+    components::{collidable::Collidable, health::Health, pickup::Pickup, player::Player},
+    utils::space::Space,
+};
+use hecs::{Entity, World};
+
+pub struct PickupCollector;
+
+impl PickupCollector {
+    pub fn run(world: &mut World, space: &Space, delta_time: f32) {
+        let mut to_despawn = Vec::<Entity>::new();
+
+        for (entity, (pickup, collidable)) in world.query::<(&mut Pickup, &Collidable)>().iter() {
+            pickup.time_left -= delta_time;
+            if pickup.time_left <= 0.0 {
+                to_despawn.push(entity);
+                continue;
+            }
+
+            let Some(space_object) = collidable.space_object.as_ref() else {
+                continue;
+            };
+            for object in space.collisions(space_object, true) {
+                let Some(other) = object.entity else {
+                    continue;
+                };
+                if world.get::<&Player>(other).is_err() {
+                    continue;
+                }
+                if let Ok(mut health) = world.get::<&mut Health>(other) {
+                    health.value = (health.value + pickup.health).min(health.limit);
+                }
+                to_despawn.push(entity);
+                break;
+            }
+        }
+
+        for entity in to_despawn {
+            let _ = world.despawn(entity);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{
+        components::damage::DamageLayer,
+        systems::collision_detector::CollisionDetector,
+        utils::{
+            space::SpaceCollider,
+            test_utils::{collidable, player},
+        },
+    };
+    use micro_games_kit::third_party::vek::{Transform, Vec2};
+
+    #[test]
+    fn test_pickup_heals_player() {
+        let mut world = World::new();
+        let mut space = Space::default();
+        let player = world.spawn((
+            player(),
+            Transform::<f32, f32, f32>::default(),
+            collidable(SpaceCollider::circle(10.0)),
+            Health {
+                value: 90.0,
+                limit: 100.0,
+                layer: DamageLayer::None,
+            },
+        ));
+        let near = world.spawn((
+            Pickup {
+                health: 20.0,
+                time_left: 10.0,
+            },
+            Transform::<f32, f32, f32> {
+                position: Vec2::new(15.0, 0.0).into(),
+                ..Default::default()
+            },
+            collidable(SpaceCollider::circle(10.0)),
+        ));
+        let far = world.spawn((
+            Pickup {
+                health: 20.0,
+                time_left: 1.0,
+            },
+            Transform::<f32, f32, f32> {
+                position: Vec2::new(500.0, 0.0).into(),
+                ..Default::default()
+            },
+            collidable(SpaceCollider::circle(10.0)),
+        ));
+
+        CollisionDetector::run(&world, &mut space);
+        PickupCollector::run(&mut world, &space, 0.5);
+        assert_eq!(world.get::<&Health>(player).unwrap().value, 100.0);
+        assert!(!world.contains(near));
+        assert!(world.contains(far));
+
+        // pickups left on the ground fade away.
+        CollisionDetector::run(&world, &mut space);
+        PickupCollector::run(&mut world, &space, 0.5);
+        assert!(!world.contains(far));
+    }
+}
@@ -1,37 +1,353 @@
 use crate::game::{
     components::{
-        animation::Animation, effect::Effect, follow_player::FollowPlayer,
-        ignore_entity::IgnoreEntity, immobility::Immobility, particle::Particle, player::Player,
-        spell::Spell,
+        animation::Animation, buff::Buffs, effect::Effect, enemy::Enemy,
+        follow_player::FollowPlayer, ignore_entity::IgnoreEntity, immobility::Immobility,
+        particle::Particle, player::Player, spell::Spell,
     },
     states::new_gameplay::NewGameplay,
-    utils::magic::{database::WordToSpellTagDatabase, spell_tag::SpellTagEffect},
+    utils::{
+        audio::Audio,
+        magic::{
+            database::WordToSpellTagDatabase,
+            spell_tag::{SpellTagEffect, SpellTagTarget},
+            spellbook::Spellbook,
+        },
+        screen_to_world,
+    },
 };
 use hecs::{Entity, World};
 use micro_games_kit::{
     animation::{FrameAnimation, NamedAnimation},
     context::GameContext,
+    gamepad::{GamepadInput, GamepadInputAxis},
     third_party::{
+        gilrs::{Axis, Button},
         spitfire_input::{
             InputActionRef, InputAxisRef, InputContext, InputMapping, VirtualAction, VirtualAxis,
         },
         vek::{Transform, Vec2},
-        windowing::event::MouseButton,
+        windowing::event::{MouseButton, VirtualKeyCode},
     },
 };
 
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum AimMode {
+    /// Aim where the mouse points, also when standing still.
+    #[default]
+    Mouse,
+    /// Aim with movement keys.
+    Keyboard,
+    /// Aim at the nearest enemy in range, otherwise where the mouse points.
+    Auto,
+}
+
+impl AimMode {
+    pub fn next(self) -> Self {
+        match self {
+            Self::Mouse => Self::Keyboard,
+            Self::Keyboard => Self::Auto,
+            Self::Auto => Self::Mouse,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Mouse => "Mouse",
+            Self::Keyboard => "Keyboard",
+            Self::Auto => "Auto",
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum InputMode {
+    /// Walk towards the mouse, letter keys type spells.
+    #[default]
+    Mouse,
+    /// Walk with arrow keys, letter keys type spells.
+    Arrows,
+    /// Toggle key switches between walking with WASD or arrows and typing spells.
+    Toggle,
+}
+
+impl InputMode {
+    pub fn next(self) -> Self {
+        match self {
+            Self::Mouse => Self::Arrows,
+            Self::Arrows => Self::Toggle,
+            Self::Toggle => Self::Mouse,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Mouse => "Mouse",
+            Self::Arrows => "Arrows",
+            Self::Toggle => "Toggle",
+        }
+    }
+}
+
+/// Keys and buttons player input is read from, turned into `InputMapping`
+/// when controller gets initialized.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlayerBindings {
+    pub attack: VirtualAction,
+    /// Movement and aim keys that do not type letters.
+    pub left: VirtualAction,
+    pub right: VirtualAction,
+    pub up: VirtualAction,
+    pub down: VirtualAction,
+    /// Movement keys that type letters, used only in toggled move mode.
+    pub alt_left: VirtualAction,
+    pub alt_right: VirtualAction,
+    pub alt_up: VirtualAction,
+    pub alt_down: VirtualAction,
+    pub toggle_incant: VirtualAction,
+    pub next_input_mode: VirtualAction,
+    pub next_aim_mode: VirtualAction,
+    /// Basic attack, or adds selected word when word picker is open.
+    pub pad_confirm: Button,
+    /// Removes last word of incantation.
+    pub pad_undo: Button,
+    pub pad_cast: Button,
+    pub pad_picker: Button,
+    pub pad_previous_group: Button,
+    pub pad_next_group: Button,
+    pub pad_deadzone: f32,
+}
+
+impl Default for PlayerBindings {
+    fn default() -> Self {
+        Self {
+            attack: VirtualAction::MouseButton(MouseButton::Left),
+            left: VirtualAction::KeyButton(VirtualKeyCode::Left),
+            right: VirtualAction::KeyButton(VirtualKeyCode::Right),
+            up: VirtualAction::KeyButton(VirtualKeyCode::Up),
+            down: VirtualAction::KeyButton(VirtualKeyCode::Down),
+            alt_left: VirtualAction::KeyButton(VirtualKeyCode::A),
+            alt_right: VirtualAction::KeyButton(VirtualKeyCode::D),
+            alt_up: VirtualAction::KeyButton(VirtualKeyCode::W),
+            alt_down: VirtualAction::KeyButton(VirtualKeyCode::S),
+            toggle_incant: VirtualAction::KeyButton(VirtualKeyCode::Tab),
+            next_input_mode: VirtualAction::KeyButton(VirtualKeyCode::F1),
+            next_aim_mode: VirtualAction::KeyButton(VirtualKeyCode::F2),
+            pad_confirm: Button::South,
+            pad_undo: Button::East,
+            pad_cast: Button::RightTrigger2,
+            pad_picker: Button::North,
+            pad_previous_group: Button::LeftTrigger,
+            pad_next_group: Button::RightTrigger,
+            pad_deadzone: 0.2,
+        }
+    }
+}
+
+impl PlayerBindings {
+    pub fn mapping(&self, input: &PlayerInput) -> InputMapping {
+        InputMapping::default()
+            .action(self.attack, input.attack_action.clone())
+            .axis(VirtualAxis::MousePositionX, input.mouse_x.clone())
+            .axis(VirtualAxis::MousePositionY, input.mouse_y.clone())
+            .action(self.left, input.left.clone())
+            .action(self.right, input.right.clone())
+            .action(self.up, input.up.clone())
+            .action(self.down, input.down.clone())
+            .action(self.alt_left, input.alt_left.clone())
+            .action(self.alt_right, input.alt_right.clone())
+            .action(self.alt_up, input.alt_up.clone())
+            .action(self.alt_down, input.alt_down.clone())
+            .action(self.toggle_incant, input.toggle_incant.clone())
+            .action(self.next_input_mode, input.next_input_mode.clone())
+            .action(self.next_aim_mode, input.next_aim_mode.clone())
+    }
+
+    /// Human readable name of the key or button bound to the action.
+    pub fn action_name(action: VirtualAction) -> String {
+        match action {
+            VirtualAction::KeyButton(key) => format!("{key:?}"),
+            VirtualAction::MouseButton(button) => format!("{button:?} mouse button"),
+            VirtualAction::Axis(axis) => format!("Axis {axis}"),
+        }
+    }
+
+    /// Movement on left stick, aim and word picking on right stick.
+    pub fn gamepad(&self, input: &PlayerInput) -> GamepadInput {
+        GamepadInput::default()
+            .auto_acquire()
+            .axis(
+                Axis::LeftStickX,
+                GamepadInputAxis::double(
+                    input.pad_move_left.clone(),
+                    input.pad_move_right.clone(),
+                    self.pad_deadzone,
+                ),
+            )
+            .axis(
+                Axis::LeftStickY,
+                GamepadInputAxis::double(
+                    input.pad_move_down.clone(),
+                    input.pad_move_up.clone(),
+                    self.pad_deadzone,
+                ),
+            )
+            .axis(
+                Axis::RightStickX,
+                GamepadInputAxis::double(
+                    input.pad_aim_left.clone(),
+                    input.pad_aim_right.clone(),
+                    self.pad_deadzone,
+                ),
+            )
+            .axis(
+                Axis::RightStickY,
+                GamepadInputAxis::double(
+                    input.pad_aim_down.clone(),
+                    input.pad_aim_up.clone(),
+                    self.pad_deadzone,
+                ),
+            )
+            .button(self.pad_confirm, input.pad_confirm.clone())
+            .button(self.pad_undo, input.pad_undo.clone())
+            .button(self.pad_cast, input.pad_cast.clone())
+            .button(self.pad_picker, input.pad_picker.clone())
+            .button(self.pad_previous_group, input.pad_previous_group.clone())
+            .button(self.pad_next_group, input.pad_next_group.clone())
+    }
+}
+
+#[derive(Default)]
 pub struct PlayerInput {
     pub attack_action: InputActionRef,
     pub mouse_x: InputAxisRef,
     pub mouse_y: InputAxisRef,
+    pub left: InputActionRef,
+    pub right: InputActionRef,
+    pub up: InputActionRef,
+    pub down: InputActionRef,
+    pub alt_left: InputActionRef,
+    pub alt_right: InputActionRef,
+    pub alt_up: InputActionRef,
+    pub alt_down: InputActionRef,
+    pub toggle_incant: InputActionRef,
+    pub next_input_mode: InputActionRef,
+    pub next_aim_mode: InputActionRef,
+    pub pad_move_left: InputAxisRef,
+    pub pad_move_right: InputAxisRef,
+    pub pad_move_up: InputAxisRef,
+    pub pad_move_down: InputAxisRef,
+    pub pad_aim_left: InputAxisRef,
+    pub pad_aim_right: InputAxisRef,
+    pub pad_aim_up: InputAxisRef,
+    pub pad_aim_down: InputAxisRef,
+    pub pad_confirm: InputActionRef,
+    pub pad_undo: InputActionRef,
+    pub pad_cast: InputActionRef,
+    pub pad_picker: InputActionRef,
+    pub pad_previous_group: InputActionRef,
+    pub pad_next_group: InputActionRef,
+}
+
+impl PlayerInput {
+    fn arrows(&self) -> Vec2<f32> {
+        Vec2::new(
+            PlayerController::axis(self.left.get().is_down(), self.right.get().is_down()),
+            PlayerController::axis(self.up.get().is_down(), self.down.get().is_down()),
+        )
+    }
+
+    fn alt_arrows(&self) -> Vec2<f32> {
+        Vec2::new(
+            PlayerController::axis(
+                self.alt_left.get().is_down(),
+                self.alt_right.get().is_down(),
+            ),
+            PlayerController::axis(self.alt_up.get().is_down(), self.alt_down.get().is_down()),
+        )
+    }
+
+    fn pad_move(&self) -> Vec2<f32> {
+        Vec2::new(
+            self.pad_move_right.get().0 - self.pad_move_left.get().0,
+            self.pad_move_down.get().0 - self.pad_move_up.get().0,
+        )
+    }
+
+    fn pad_aim(&self) -> Vec2<f32> {
+        Vec2::new(
+            self.pad_aim_right.get().0 - self.pad_aim_left.get().0,
+            self.pad_aim_down.get().0 - self.pad_aim_up.get().0,
+        )
+    }
+}
+
+/// Radial menu for building incantation out of discovered words, one group
+/// of words per spell tag category.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct WordPicker {
+    pub open: bool,
+    pub group: usize,
+    pub selected: Option<usize>,
+}
+
+impl WordPicker {
+    /// Stick has to be pushed at least this far to change selection.
+    pub const DEADZONE: f32 = 0.5;
+
+    pub fn toggle(&mut self) {
+        self.open = !self.open;
+        self.selected = None;
+    }
+
+    pub fn cycle_group(&mut self, step: isize, count: usize) {
+        self.group = if count > 0 {
+            (self.group as isize + step).rem_euclid(count as isize) as usize
+        } else {
+            0
+        };
+        self.selected = None;
+    }
+
+    /// Selects word in direction stick points to, words are laid out
+    /// clockwise starting from the top.
+    pub fn point(&mut self, stick: Vec2<f32>, count: usize) {
+        if count == 0 {
+            self.selected = None;
+            return;
+        }
+        if stick.magnitude() < Self::DEADZONE {
+            return;
+        }
+        let sector = std::f32::consts::TAU / count as f32;
+        let angle = stick.x.atan2(-stick.y).rem_euclid(std::f32::consts::TAU);
+        self.selected = Some(((angle + sector * 0.5) / sector) as usize % count);
+    }
+
+    /// Direction from menu center to word slot, matching `point`.
+    pub fn slot_direction(index: usize, count: usize) -> Vec2<f32> {
+        let angle = std::f32::consts::TAU * index as f32 / count.max(1) as f32;
+        Vec2::new(angle.sin(), -angle.cos())
+    }
 }
 
 pub struct PlayerController {
     pub input: Option<PlayerInput>,
+    pub gamepad: Option<GamepadInput>,
+    pub picker: WordPicker,
+    pub bindings: PlayerBindings,
+    pub input_mode: InputMode,
+    /// In toggle input mode tells if keys type spell instead of moving.
+    pub incanting: bool,
     pub run_animation: NamedAnimation,
     pub idle_animation: NamedAnimation,
     pub spell_text: String,
     pub walk_area: f32,
+    pub aim_mode: AimMode,
+    /// Last valid aim direction, always normalized.
+    pub aim: Vec2<f32>,
+    pub auto_aim_range: f32,
+    /// Maximum distance of ground targeted spells.
+    pub cast_range: f32,
 }
 
 pub struct PlayerCastAction {
@@ -40,10 +356,45 @@ pub struct PlayerCastAction {
     pub spell: Spell,
 }
 
+impl PlayerCastAction {
+    /// Cast from caster position with spell direction word applied relative
+    /// to aim: forth flies along aim, back flies behind the caster and down
+    /// lands at caster feet. Direction is always the aim, spell controller
+    /// turns velocity of backward spells around.
+    pub fn aimed(origin: Vec2<f32>, aim: Vec2<f32>, spell: Spell) -> Self {
+        let aim = aim.try_normalized().unwrap_or(Vec2::unit_x());
+        Self {
+            position: origin + aim * spell.direction.multiplier() * 15.0,
+            direction: aim,
+            spell,
+        }
+    }
+
+    /// Moves origin of ground targeted spell to target position, at most
+    /// `range` away from caster origin.
+    pub fn targeted(mut self, origin: Vec2<f32>, target: Vec2<f32>, range: f32) -> Self {
+        if self.spell.target != SpellTagTarget::Cursor {
+            return self;
+        }
+        let offset = target - origin;
+        if !offset.x.is_finite() || !offset.y.is_finite() {
+            return self;
+        }
+        self.position =
+            origin + offset.try_normalized().unwrap_or_default() * offset.magnitude().min(range);
+        self
+    }
+}
+
 impl Default for PlayerController {
     fn default() -> Self {
         PlayerController {
             input: None,
+            gamepad: None,
+            picker: Default::default(),
+            bindings: Default::default(),
+            input_mode: Default::default(),
+            incanting: false,
             run_animation: NamedAnimation {
                 animation: FrameAnimation::new(1..17).looping().playing(),
                 id: "player/walk".to_owned(),
@@ -54,74 +405,181 @@ impl Default for PlayerController {
             },
             spell_text: Default::default(),
             walk_area: 3500.0,
+            aim_mode: Default::default(),
+            aim: Vec2::unit_x(),
+            auto_aim_range: 800.0,
+            cast_range: 600.0,
         }
     }
 }
 
 impl PlayerController {
     pub fn init(&mut self, context: &mut InputContext) {
-        let attack_action = InputActionRef::default();
-        let mouse_x = InputAxisRef::default();
-        let mouse_y = InputAxisRef::default();
-
-        self.input = Some(PlayerInput {
-            attack_action: attack_action.clone(),
-            mouse_x: mouse_x.clone(),
-            mouse_y: mouse_y.clone(),
-        });
+        let input = PlayerInput::default();
+        context.push_mapping(self.bindings.mapping(&input));
+        self.gamepad = Some(self.bindings.gamepad(&input));
+        self.input = Some(input);
+    }
 
-        let mapping = InputMapping::default()
-            .action(VirtualAction::MouseButton(MouseButton::Left), attack_action)
-            .axis(VirtualAxis::MousePositionX, mouse_x)
-            .axis(VirtualAxis::MousePositionY, mouse_y);
+    /// Tells if typed characters go to the spell text.
+    pub fn accepts_typing(&self) -> bool {
+        self.input_mode != InputMode::Toggle || self.incanting
+    }
 
-        context.push_mapping(mapping);
+    pub fn mode_label(&self) -> String {
+        match (self.input_mode, self.incanting) {
+            (InputMode::Toggle, true) => "Toggle - Incant".to_owned(),
+            (InputMode::Toggle, false) => "Toggle - Move".to_owned(),
+            (mode, _) => mode.name().to_owned(),
+        }
     }
 
     pub fn run(
         &mut self,
         world: &mut World,
+        audio: &mut Audio,
         context: &mut GameContext,
         delta_time: f32,
         word_to_spell_tag_database: &WordToSpellTagDatabase,
+        spellbook: &mut Spellbook,
     ) {
         let mut cast_spell = None;
 
+        if let Some(gamepad) = self.gamepad.as_mut() {
+            gamepad.apply();
+        }
+
+        if let Some(input) = self.input.as_ref() {
+            if input.next_input_mode.get().is_pressed() {
+                self.input_mode = self.input_mode.next();
+                self.incanting = false;
+            }
+            if input.toggle_incant.get().is_pressed() && self.input_mode == InputMode::Toggle {
+                self.incanting = !self.incanting;
+            }
+            if input.next_aim_mode.get().is_pressed() {
+                self.aim_mode = self.aim_mode.next();
+            }
+
+            if input.pad_picker.get().is_pressed() {
+                self.picker.toggle();
+            }
+            if self.picker.open {
+                let groups = spellbook.groups(word_to_spell_tag_database);
+                if input.pad_previous_group.get().is_pressed() {
+                    self.picker.cycle_group(-1, groups.len());
+                }
+                if input.pad_next_group.get().is_pressed() {
+                    self.picker.cycle_group(1, groups.len());
+                }
+                let words = groups
+                    .get(self.picker.group)
+                    .map(|(_, words)| words.as_slice())
+                    .unwrap_or_default();
+                self.picker.point(input.pad_aim(), words.len());
+                if input.pad_confirm.get().is_pressed() {
+                    if let Some(word) = self.picker.selected.and_then(|index| words.get(index)) {
+                        Self::push_word(&mut self.spell_text, word);
+                    }
+                }
+            }
+            if input.pad_undo.get().is_pressed() {
+                Self::pop_word(&mut self.spell_text);
+            }
+            if input.pad_cast.get().is_pressed() {
+                cast_spell = self.incant(word_to_spell_tag_database, spellbook);
+            }
+        }
+
+        let accepts_typing = self.accepts_typing();
         if let Some(mut characters) = context.input.characters().write() {
             for character in characters.take().chars() {
+                if !accepts_typing {
+                    continue;
+                }
                 if character == '\n' || character == '\r' {
-                    cast_spell = word_to_spell_tag_database.parse(&self.spell_text);
-                    self.spell_text.clear();
+                    cast_spell = self.incant(word_to_spell_tag_database, spellbook);
                 } else if character == ' ' || character.is_alphanumeric() {
                     self.spell_text.push(character);
                 }
             }
         }
 
+        let enemy_positions = if self.aim_mode == AimMode::Auto {
+            world
+                .query::<&Transform<f32, f32, f32>>()
+                .with::<&Enemy>()
+                .iter()
+                .map(|(_, transform)| transform.position.xy())
+                .collect()
+        } else {
+            vec![]
+        };
+
         let mut cast_action: Option<(Entity, PlayerCastAction)> = None;
         let mut particles = Vec::<Particle>::new();
         let mut player_moved_vector: Option<Vec2<f32>> = None;
 
-        for (entity, (player, transform, animation, immobility, effect)) in world
+        for (entity, (player, transform, animation, immobility, effect, buffs)) in world
             .query::<(
                 &mut Player,
                 &mut Transform<f32, f32, f32>,
                 &mut Animation,
                 &Immobility,
                 &Effect,
+                Option<&Buffs>,
             )>()
             .iter()
         {
             if let Some(input) = self.input.as_ref() {
                 let mouse_pos = Vec2::new(input.mouse_x.get().0, input.mouse_y.get().0);
                 let diff = (mouse_pos - context.graphics.main_camera.screen_size / 2.0) / 200.0;
-                let length = diff.magnitude().min(1.0);
-                let movement = diff.try_normalized().unwrap_or_default() * length;
+                let keys = if accepts_typing {
+                    input.arrows()
+                } else {
+                    input.arrows() + input.alt_arrows()
+                };
+                let pad_move = input.pad_move();
+                let movement = if pad_move.is_approx_zero() {
+                    Self::movement_direction(self.input_mode, self.incanting, diff, keys)
+                } else {
+                    pad_move.try_normalized().unwrap_or_default() * pad_move.magnitude().min(1.0)
+                };
+                let position = transform.position.xy();
+                let nearest_enemy = enemy_positions
+                    .iter()
+                    .map(|enemy| *enemy - position)
+                    .filter(|offset| offset.magnitude() <= self.auto_aim_range)
+                    .min_by(|a, b| a.magnitude_squared().total_cmp(&b.magnitude_squared()));
+                self.aim = Self::aim_direction(self.aim_mode, diff, keys, nearest_enemy, self.aim);
+                // right stick aims unless it is busy picking words.
+                let pad_aim = input.pad_aim();
+                let pad_aiming = !self.picker.open && pad_aim.magnitude() >= WordPicker::DEADZONE;
+                if pad_aiming {
+                    self.aim = Self::aim_direction(AimMode::Mouse, pad_aim, keys, None, self.aim);
+                }
+                let screen_size = context.graphics.main_camera.screen_size;
+                let cursor = screen_to_world(
+                    Vec2::new(
+                        mouse_pos.x / screen_size.x * 2.0 - 1.0,
+                        1.0 - mouse_pos.y / screen_size.y * 2.0,
+                    ),
+                    context,
+                );
+                let target = match self.aim_mode {
+                    _ if pad_aiming => position + self.aim * self.cast_range,
+                    AimMode::Mouse => cursor,
+                    AimMode::Keyboard => position + self.aim * self.cast_range,
+                    AimMode::Auto => nearest_enemy
+                        .map(|offset| position + offset)
+                        .unwrap_or(cursor),
+                };
 
-                transform.scale.x = if movement.x > 0.0 { -1.0 } else { 1.0 };
+                transform.scale.x = if self.aim.x > 0.0 { -1.0 } else { 1.0 };
 
                 if movement.magnitude() > 0.5 {
-                    player_moved_vector = Some(movement * delta_time * 190.0);
+                    let speed = 190.0 * buffs.map(Buffs::speed_multiplier).unwrap_or(1.0);
+                    player_moved_vector = Some(movement * delta_time * speed);
 
                     if immobility.time_left > 0.0 {
                         player_moved_vector = Some(player_moved_vector.unwrap() * 0.5);
@@ -146,33 +604,21 @@ impl PlayerController {
                     }
                 }
 
-                if input.attack_action.get().is_pressed() {
-                    let basic_spell = Spell::basic();
-
+                if input.attack_action.get().is_pressed()
+                    || (!self.picker.open && input.pad_confirm.get().is_pressed())
+                {
                     cast_action = Some((
                         entity,
-                        PlayerCastAction {
-                            position: (transform.position
-                                + movement.normalized()
-                                    * basic_spell.direction.multiplier()
-                                    * 15.0)
-                                .into(),
-                            direction: movement.normalized(),
-                            spell: basic_spell,
-                        },
+                        PlayerCastAction::aimed(transform.position.xy(), self.aim, Spell::basic())
+                            .targeted(transform.position.xy(), target, self.cast_range),
                     ));
                 }
 
                 if let Some(spell) = cast_spell.as_ref() {
                     cast_action = Some((
                         entity,
-                        PlayerCastAction {
-                            position: (transform.position
-                                + movement.normalized() * spell.direction.multiplier() * 15.0)
-                                .into(),
-                            direction: movement.normalized(),
-                            spell: spell.clone(),
-                        },
+                        PlayerCastAction::aimed(transform.position.xy(), self.aim, spell.clone())
+                            .targeted(transform.position.xy(), target, self.cast_range),
                     ));
                 }
 
@@ -220,11 +666,306 @@ impl PlayerController {
         }
 
         if let Some(cast) = cast_action {
-            NewGameplay::cast_spell(world, cast.1, cast.0);
+            let entities = NewGameplay::cast_spell(world, audio, cast.1, cast.0);
+            NewGameplay::befriend(world, &entities);
         }
 
         for (_, (ignore_player,)) in world.query::<(&mut IgnoreEntity,)>().iter() {
             ignore_player.ignore_time = (ignore_player.ignore_time - delta_time).max(0.0);
         }
     }
+
+    /// Picks aim from source of given mode, keeping previous aim when source
+    /// gives no direction, so aim is never zero nor NaN.
+    pub fn aim_direction(
+        mode: AimMode,
+        mouse: Vec2<f32>,
+        keys: Vec2<f32>,
+        nearest_enemy: Option<Vec2<f32>>,
+        previous: Vec2<f32>,
+    ) -> Vec2<f32> {
+        let source = match mode {
+            AimMode::Mouse => mouse,
+            AimMode::Keyboard => keys,
+            AimMode::Auto => nearest_enemy.unwrap_or(mouse),
+        };
+        source
+            .try_normalized()
+            .filter(|direction| direction.x.is_finite() && direction.y.is_finite())
+            .or_else(|| previous.try_normalized())
+            .unwrap_or(Vec2::unit_x())
+    }
+
+    /// Casts spell text the same way regardless if it was typed or picked,
+    /// words in it become discovered.
+    fn incant(
+        &mut self,
+        database: &WordToSpellTagDatabase,
+        spellbook: &mut Spellbook,
+    ) -> Option<Spell> {
+        let text = std::mem::take(&mut self.spell_text);
+        spellbook.discover(database, &text);
+        database.parse(&text)
+    }
+
+    fn push_word(text: &mut String, word: &str) {
+        if !text.is_empty() && !text.ends_with(' ') {
+            text.push(' ');
+        }
+        text.push_str(word);
+    }
+
+    fn pop_word(text: &mut String) {
+        let trimmed = text.trim_end();
+        let length = trimmed.rfind(' ').unwrap_or_default();
+        text.truncate(length);
+    }
+
+    /// Movement of length up to 1, where above 0.5 means walking.
+    pub fn movement_direction(
+        mode: InputMode,
+        incanting: bool,
+        mouse: Vec2<f32>,
+        keys: Vec2<f32>,
+    ) -> Vec2<f32> {
+        match mode {
+            InputMode::Mouse => {
+                mouse.try_normalized().unwrap_or_default() * mouse.magnitude().min(1.0)
+            }
+            InputMode::Arrows => keys.try_normalized().unwrap_or_default(),
+            InputMode::Toggle if incanting => Vec2::zero(),
+            InputMode::Toggle => keys.try_normalized().unwrap_or_default(),
+        }
+    }
+
+    fn axis(negative: bool, positive: bool) -> f32 {
+        positive as i8 as f32 - negative as i8 as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{
+        components::projectile::Projectile,
+        systems::spell_controller::SpellController,
+        utils::magic::spell_tag::{SpellTag, SpellTagDirection, SpellTagShape, SpellTagSize},
+    };
+
+    #[test]
+    fn test_action_name() {
+        let mut bindings = PlayerBindings::default();
+        assert_eq!(PlayerBindings::action_name(bindings.toggle_incant), "Tab");
+        bindings.toggle_incant = VirtualAction::KeyButton(VirtualKeyCode::Space);
+        assert_eq!(PlayerBindings::action_name(bindings.toggle_incant), "Space");
+        assert_eq!(
+            PlayerBindings::action_name(bindings.attack),
+            "Left mouse button"
+        );
+    }
+
+    #[test]
+    fn test_aim_direction() {
+        let previous = Vec2::new(0.0, -1.0);
+        let enemy = Some(Vec2::new(0.0, 300.0));
+
+        // standing still with mouse close to the player still aims there.
+        let aim = PlayerController::aim_direction(
+            AimMode::Mouse,
+            Vec2::new(0.1, 0.0),
+            Vec2::zero(),
+            None,
+            previous,
+        );
+        assert_eq!(aim, Vec2::unit_x());
+        for mode in [AimMode::Mouse, AimMode::Keyboard, AimMode::Auto] {
+            let aim =
+                PlayerController::aim_direction(mode, Vec2::zero(), Vec2::zero(), None, previous);
+            assert_eq!(aim, previous);
+            let aim = PlayerController::aim_direction(
+                mode,
+                Vec2::broadcast(f32::NAN),
+                Vec2::broadcast(f32::NAN),
+                None,
+                Vec2::zero(),
+            );
+            assert_eq!(aim, Vec2::unit_x());
+        }
+        let aim = PlayerController::aim_direction(
+            AimMode::Keyboard,
+            Vec2::unit_x(),
+            Vec2::new(-1.0, 0.0),
+            enemy,
+            previous,
+        );
+        assert_eq!(aim, Vec2::new(-1.0, 0.0));
+        let aim = PlayerController::aim_direction(
+            AimMode::Auto,
+            Vec2::unit_x(),
+            Vec2::zero(),
+            enemy,
+            previous,
+        );
+        assert_eq!(aim, Vec2::unit_y());
+        let aim = PlayerController::aim_direction(
+            AimMode::Auto,
+            Vec2::unit_x(),
+            Vec2::zero(),
+            None,
+            previous,
+        );
+        assert_eq!(aim, Vec2::unit_x());
+    }
+
+    #[test]
+    fn test_input_modes() {
+        let mouse = Vec2::new(2.0, 0.0);
+        let keys = Vec2::new(-1.0, -1.0);
+        let diagonal = keys.normalized();
+
+        assert_eq!(
+            PlayerController::movement_direction(InputMode::Mouse, false, mouse, keys),
+            Vec2::unit_x()
+        );
+        assert_eq!(
+            PlayerController::movement_direction(InputMode::Mouse, false, mouse * 0.1, keys),
+            Vec2::unit_x() * 0.2
+        );
+        assert_eq!(
+            PlayerController::movement_direction(InputMode::Arrows, false, mouse, keys),
+            diagonal
+        );
+        assert_eq!(
+            PlayerController::movement_direction(InputMode::Toggle, false, mouse, keys),
+            diagonal
+        );
+        assert_eq!(
+            PlayerController::movement_direction(InputMode::Toggle, true, mouse, keys),
+            Vec2::zero()
+        );
+        assert_eq!(
+            PlayerController::movement_direction(InputMode::Arrows, false, mouse, Vec2::zero()),
+            Vec2::zero()
+        );
+
+        let mut controller = PlayerController::default();
+        assert!(controller.accepts_typing());
+        controller.input_mode = InputMode::Arrows;
+        assert!(controller.accepts_typing());
+        controller.input_mode = InputMode::Toggle;
+        assert!(!controller.accepts_typing());
+        assert_eq!(controller.mode_label(), "Toggle - Move");
+        controller.incanting = true;
+        assert!(controller.accepts_typing());
+        assert_eq!(controller.mode_label(), "Toggle - Incant");
+    }
+
+    #[test]
+    fn test_word_picker() {
+        let database = WordToSpellTagDatabase::default()
+            .with("fire", SpellTag::Effect(SpellTagEffect::Fire))
+            .with("wet", SpellTag::Effect(SpellTagEffect::Water))
+            .with("zap", SpellTag::Effect(SpellTagEffect::Electric))
+            .with("big", SpellTag::Size(SpellTagSize::Large))
+            .with("ball", SpellTag::Shape(SpellTagShape::Point));
+        let mut spellbook = Spellbook::starter(&database);
+        spellbook.discover(&database, "big");
+        let groups = spellbook.groups(&database);
+        assert_eq!(groups.len(), 2);
+
+        let mut picker = WordPicker::default();
+        picker.toggle();
+        assert!(picker.open);
+        picker.cycle_group(-1, groups.len());
+        assert_eq!(picker.group, 1);
+        picker.cycle_group(1, groups.len());
+        assert_eq!(picker.group, 0);
+
+        // three effect words: top, bottom right and bottom left.
+        let count = groups[0].1.len();
+        picker.point(Vec2::new(0.0, -1.0), count);
+        assert_eq!(picker.selected, Some(0));
+        picker.point(Vec2::new(0.8, 0.6), count);
+        assert_eq!(picker.selected, Some(1));
+        picker.point(Vec2::new(-0.8, 0.6), count);
+        assert_eq!(picker.selected, Some(2));
+        picker.point(Vec2::new(0.1, 0.0), count);
+        assert_eq!(picker.selected, Some(2));
+        for index in 0..count {
+            picker.point(WordPicker::slot_direction(index, count), count);
+            assert_eq!(picker.selected, Some(index));
+        }
+
+        // picked words produce the same spell as typed ones.
+        let mut controller = PlayerController::default();
+        PlayerController::push_word(&mut controller.spell_text, &groups[1].1[0]);
+        PlayerController::push_word(&mut controller.spell_text, "wet");
+        PlayerController::push_word(&mut controller.spell_text, "fire");
+        PlayerController::pop_word(&mut controller.spell_text);
+        assert_eq!(controller.spell_text, "big wet");
+        let picked = controller.incant(&database, &mut spellbook);
+        assert!(controller.spell_text.is_empty());
+        assert_eq!(picked, database.parse("big wet"));
+        assert_eq!(picked.unwrap().size, SpellTagSize::Large);
+
+        // typed words get discovered.
+        controller.spell_text = "ball".to_owned();
+        controller.incant(&database, &mut spellbook);
+        assert!(spellbook.contains("ball"));
+    }
+
+    #[test]
+    fn test_cast_relative_to_aim() {
+        let origin = Vec2::new(100.0, 100.0);
+        let aim = Vec2::unit_y();
+        let spell = |direction| Spell {
+            direction,
+            shape: SpellTagShape::Point,
+            ..Spell::basic()
+        };
+
+        let cast = PlayerCastAction::aimed(origin, aim, spell(SpellTagDirection::Forward));
+        assert_eq!(cast.position, Vec2::new(100.0, 115.0));
+        assert_eq!(cast.direction, aim);
+        let cast = PlayerCastAction::aimed(origin, aim, spell(SpellTagDirection::Backward));
+        assert_eq!(cast.position, Vec2::new(100.0, 85.0));
+        assert_eq!(cast.direction, aim);
+        let mut world = World::new();
+        let projectile = world.spawn((
+            Projectile::new(100.0, cast.direction, 1.0),
+            cast.spell.clone(),
+        ));
+        SpellController::run(&mut world);
+        assert_eq!(
+            world.get::<&Projectile>(projectile).unwrap().velocity,
+            -aim * 100.0
+        );
+        let cast = PlayerCastAction::aimed(origin, aim, spell(SpellTagDirection::Down));
+        assert_eq!(cast.position, origin);
+        assert_eq!(cast.direction, aim);
+        let cast = PlayerCastAction::aimed(origin, Vec2::zero(), spell(SpellTagDirection::Forward));
+        assert!(cast.direction.x.is_finite() && cast.position.x.is_finite());
+
+        // ground targeted spells start at target, limited by cast range.
+        let targeted = |direction, target| {
+            let spell = Spell {
+                target: SpellTagTarget::Cursor,
+                ..spell(direction)
+            };
+            PlayerCastAction::aimed(origin, aim, spell).targeted(origin, target, 200.0)
+        };
+        let cast = targeted(SpellTagDirection::Down, Vec2::new(150.0, 100.0));
+        assert_eq!(cast.position, Vec2::new(150.0, 100.0));
+        assert_eq!(cast.direction, aim);
+        let cast = targeted(SpellTagDirection::Forward, Vec2::new(100.0, 1000.0));
+        assert_eq!(cast.position, Vec2::new(100.0, 300.0));
+        let cast = targeted(SpellTagDirection::Down, Vec2::broadcast(f32::NAN));
+        assert_eq!(cast.position, origin);
+        let cast = PlayerCastAction::aimed(origin, aim, spell(SpellTagDirection::Down)).targeted(
+            origin,
+            Vec2::new(150.0, 100.0),
+            200.0,
+        );
+        assert_eq!(cast.position, origin);
+    }
 }
@@ -1,14 +1,26 @@
 use crate::game::{
-    components::{collidable::Collidable, ignore_entity::IgnoreEntity, projectile::Projectile},
+    components::{
+        affix::Reflective,
+        collidable::Collidable,
+        damage::Damage,
+        health::Health,
+        ignore_entity::IgnoreEntity,
+        knockback::Knockback,
+        obstacle::{Obstacle, ObstacleProjectileResponse},
+        projectile::Projectile,
+        spell::Spell,
+        wall::Wall,
+    },
+    systems::damage_dealer::DamageDealer,
     utils::space::Space,
 };
 use hecs::{Entity, World};
-use micro_games_kit::third_party::vek::Transform;
+use micro_games_kit::third_party::vek::{Transform, Vec2};
 
 pub struct ProjectileController;
 
 impl ProjectileController {
-    pub fn run(world: &mut World, delta_time: f32) {
+    pub fn run(world: &mut World, space: &Space, delta_time: f32) {
         let mut to_despawn = Vec::<Entity>::new();
 
         for (entity, (projectile, transform)) in world
@@ -28,8 +40,6 @@ impl ProjectileController {
             let _ = world.despawn(entity);
         }
 
-        let space = Space::read();
-        let space = space.read().unwrap();
         let mut entities_to_remove = Vec::<(Entity, Entity)>::new();
 
         for (projectile_entity, (collidable, _)) in
@@ -50,14 +60,306 @@ impl ProjectileController {
             }
         }
 
+        let mut to_stop = Vec::<Entity>::new();
+
         for (projectile_entity, entity_b) in entities_to_remove {
+            // clashing projectiles are resolved by spell clash.
             if world.get::<&Projectile>(entity_b).is_ok() {
                 continue;
             }
 
+            if Reflective::reflects(world, projectile_entity, entity_b) {
+                Self::reflect(world, projectile_entity, entity_b);
+                continue;
+            }
+
+            if let Ok(obstacle) = world.get::<&Obstacle>(entity_b).map(|obstacle| *obstacle) {
+                if Wall::passes(world, entity_b, projectile_entity) {
+                    continue;
+                }
+                // destructible obstacles take damage of stopped projectiles.
+                if let Ok(damage) = world
+                    .get::<&Damage>(projectile_entity)
+                    .map(|damage| *damage)
+                {
+                    DamageDealer::deal(world, projectile_entity, damage, entity_b);
+                }
+                let bounced = obstacle.projectile_response == ObstacleProjectileResponse::Bounce
+                    && Self::bounce(world, projectile_entity, entity_b);
+                if !bounced {
+                    to_stop.push(projectile_entity);
+                }
+                continue;
+            }
+
+            // friendly spells fly through friends.
+            if !Self::harms(world, projectile_entity, entity_b) {
+                continue;
+            }
+
             if let Ok(projectile) = world.get::<&mut Projectile>(projectile_entity).as_mut() {
                 projectile.ttl -= 0.2;
             }
+
+            // big spells knock what they hit back, once per hit entity even
+            // when they overlap it for several frames.
+            let impulse = world
+                .query_one::<(&mut Projectile, &Spell)>(projectile_entity)
+                .ok()
+                .and_then(|mut query| {
+                    let (projectile, spell) = query.get()?;
+                    let impulse = projectile.velocity.try_normalized().unwrap_or_default()
+                        * spell.size.push_impulse();
+                    if impulse == Vec2::zero() || projectile.pushed.contains(&entity_b) {
+                        return None;
+                    }
+                    projectile.pushed.push(entity_b);
+                    Some(impulse)
+                });
+            if let Some(impulse) = impulse {
+                Knockback::push(world, entity_b, impulse);
+            }
+        }
+
+        for entity in to_stop {
+            let _ = world.despawn(entity);
+        }
+    }
+
+    fn harms(world: &World, projectile_entity: Entity, entity: Entity) -> bool {
+        let Ok(damage) = world.get::<&Damage>(projectile_entity) else {
+            return true;
+        };
+        let Ok(health) = world.get::<&Health>(entity) else {
+            return true;
+        };
+        damage.layer.allow_damage(health.layer)
+    }
+
+    // Sends projectile back and makes it harmless for the reflecting entity for
+    // a moment, so it can hit the caster instead.
+    fn reflect(world: &mut World, projectile_entity: Entity, reflecting_entity: Entity) {
+        if let Ok(mut projectile) = world.get::<&mut Projectile>(projectile_entity) {
+            projectile.direction = -projectile.direction;
+            projectile.velocity = -projectile.velocity;
+        }
+        // reflected spell now belongs to the side of the reflecting entity.
+        if let Ok(layer) = world
+            .get::<&Health>(reflecting_entity)
+            .map(|health| health.layer)
+        {
+            if let Ok(mut damage) = world.get::<&mut Damage>(projectile_entity) {
+                damage.layer = layer;
+            }
+        }
+        let _ = world.insert_one(
+            projectile_entity,
+            IgnoreEntity {
+                ignore_time: 0.5,
+                ignored_entity: reflecting_entity,
+            },
+        );
+    }
+
+    // Reflects projectile off the obstacle surface, returns false when contact
+    // cannot be resolved (non-circle projectile shapes) so it gets stopped.
+    fn bounce(world: &World, projectile_entity: Entity, obstacle_entity: Entity) -> bool {
+        let Some(obstacle) = world
+            .get::<&Collidable>(obstacle_entity)
+            .ok()
+            .and_then(|collidable| collidable.space_object.clone())
+        else {
+            return false;
+        };
+        let Ok(mut query) = world
+            .query_one::<(&Collidable, &mut Projectile, &mut Transform<f32, f32, f32>)>(
+                projectile_entity,
+            )
+        else {
+            return false;
+        };
+        let Some((collidable, projectile, transform)) = query.get() else {
+            return false;
+        };
+        let Some(mut object) = collidable.space_object.clone() else {
+            return false;
+        };
+        object.position = transform.position.xy();
+        let Some(push) = object.contact(&obstacle) else {
+            return false;
+        };
+        let normal = push.try_normalized().unwrap_or_default();
+
+        if projectile.velocity.dot(normal) < 0.0 {
+            projectile.direction -= normal * 2.0 * projectile.direction.dot(normal);
+            projectile.velocity -= normal * 2.0 * projectile.velocity.dot(normal);
+        }
+        transform.position += push;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{
+        components::{damage::DamageLayer, velocity::Velocity},
+        systems::{collision_detector::CollisionDetector, damage_dealer::DamageDealer},
+        utils::{magic::spell_tag::SpellTagSize, space::SpaceCollider, test_utils::collidable},
+    };
+    use micro_games_kit::third_party::vek::Vec2;
+
+    #[test]
+    fn test_reflective_sends_projectiles_back() {
+        let mut world = World::new();
+        let mut space = Space::default();
+        let caster = world.spawn((
+            Transform::<f32, f32, f32> {
+                position: Vec2::new(-100.0, 0.0).into(),
+                ..Default::default()
+            },
+            collidable(SpaceCollider::circle(10.0)),
+            Health {
+                value: 10.0,
+                limit: 10.0,
+                layer: DamageLayer::None,
+            },
+        ));
+        let enemy = world.spawn((
+            Transform::<f32, f32, f32>::default(),
+            collidable(SpaceCollider::circle(10.0)),
+            Health {
+                value: 10.0,
+                limit: 10.0,
+                layer: DamageLayer::Enemy,
+            },
+            Reflective,
+        ));
+        let projectile = world.spawn((
+            Transform::<f32, f32, f32> {
+                position: Vec2::new(-15.0, 0.0).into(),
+                ..Default::default()
+            },
+            collidable(SpaceCollider::circle(10.0)),
+            Projectile::new(100.0, Vec2::unit_x(), 5.0),
+            Damage {
+                value: 5.0,
+                layer: DamageLayer::None,
+            },
+            IgnoreEntity {
+                ignore_time: 0.5,
+                ignored_entity: caster,
+            },
+        ));
+
+        // touching projectile is not harmful and turns back.
+        CollisionDetector::run(&world, &mut space);
+        DamageDealer::run(&world, &space);
+        ProjectileController::run(&mut world, &space, 0.01);
+        CollisionDetector::run(&world, &mut space);
+        DamageDealer::run(&world, &space);
+        assert_eq!(world.get::<&Health>(enemy).unwrap().value, 10.0);
+        assert_eq!(
+            world.get::<&Projectile>(projectile).unwrap().direction,
+            -Vec2::unit_x()
+        );
+        assert_eq!(
+            world
+                .get::<&IgnoreEntity>(projectile)
+                .unwrap()
+                .ignored_entity,
+            enemy
+        );
+        assert_eq!(world.get::<&Projectile>(projectile).unwrap().ttl, 4.99);
+
+        // reflected projectile takes side of the reflecting enemy and hurts
+        // its caster.
+        assert_eq!(
+            world.get::<&Damage>(projectile).unwrap().layer,
+            DamageLayer::Enemy
+        );
+        world
+            .get::<&mut Transform<f32, f32, f32>>(projectile)
+            .unwrap()
+            .position = Vec2::new(-100.0, 0.0).into();
+        CollisionDetector::run(&world, &mut space);
+        DamageDealer::run(&world, &space);
+        assert_eq!(world.get::<&Health>(caster).unwrap().value, 5.0);
+    }
+
+    #[test]
+    fn test_reflective_lets_allied_projectiles_through() {
+        let mut world = World::new();
+        let mut space = Space::default();
+        let ally = world.spawn((
+            Transform::<f32, f32, f32>::default(),
+            collidable(SpaceCollider::circle(10.0)),
+            Health {
+                value: 10.0,
+                limit: 10.0,
+                layer: DamageLayer::Enemy,
+            },
+            Reflective,
+        ));
+        let projectile = world.spawn((
+            Transform::<f32, f32, f32> {
+                position: Vec2::new(-15.0, 0.0).into(),
+                ..Default::default()
+            },
+            collidable(SpaceCollider::circle(10.0)),
+            Projectile::new(100.0, Vec2::unit_x(), 5.0),
+            Damage {
+                value: 5.0,
+                layer: DamageLayer::Enemy,
+            },
+        ));
+
+        CollisionDetector::run(&world, &mut space);
+        DamageDealer::run(&world, &space);
+        ProjectileController::run(&mut world, &space, 0.01);
+        assert_eq!(world.get::<&Health>(ally).unwrap().value, 10.0);
+        assert_eq!(
+            world.get::<&Projectile>(projectile).unwrap().direction,
+            Vec2::unit_x()
+        );
+        assert_eq!(
+            world.get::<&Damage>(projectile).unwrap().layer,
+            DamageLayer::Enemy
+        );
+        assert!(world.get::<&IgnoreEntity>(projectile).is_err());
+    }
+
+    #[test]
+    fn test_big_projectiles_push_once() {
+        let mut world = World::new();
+        let mut space = Space::default();
+        let target = world.spawn((
+            Transform::<f32, f32, f32>::default(),
+            collidable(SpaceCollider::circle(50.0)),
+            Velocity::default(),
+            Knockback::default(),
+        ));
+        world.spawn((
+            Transform::<f32, f32, f32> {
+                position: Vec2::new(-20.0, 0.0).into(),
+                ..Default::default()
+            },
+            collidable(SpaceCollider::circle(80.0)),
+            Projectile::new(1.0, Vec2::unit_x(), 5.0),
+            Spell {
+                size: SpellTagSize::Large,
+                ..Spell::basic()
+            },
+        ));
+
+        // stays on target for many frames but knocks it back only once.
+        for _ in 0..10 {
+            CollisionDetector::run(&world, &mut space);
+            ProjectileController::run(&mut world, &space, 0.01);
         }
+        assert_eq!(
+            world.get::<&Velocity>(target).unwrap().value,
+            Vec2::new(SpellTagSize::Large.push_impulse(), 0.0)
+        );
     }
 }
@@ -0,0 +1,297 @@
+use crate::game::{
+    components::{
+        collidable::Collidable,
+        damage::{Damage, DamageLayer},
+        particle::Particle,
+        projectile::Projectile,
+        spell::Spell,
+    },
+    utils::{audio::Audio, magic::spell_tag::SpellTagSize, space::Space},
+};
+use hecs::{Entity, World};
+use micro_games_kit::third_party::vek::{Transform, Vec2};
+use std::collections::HashSet;
+
+/// Strength multiplier of spell whose element counters element of the other.
+const COUNTER_MULTIPLIER: f32 = 2.0;
+/// Clashing spells closer in strength than this fraction cancel each other.
+const ANNIHILATION_MARGIN: f32 = 0.1;
+
+/// What happened to spells running into each other.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ClashOutcome {
+    /// Both spells are gone.
+    Annihilate,
+    /// Loser is gone, winner keeps going with damage multiplied by factor.
+    Weaken {
+        loser: Entity,
+        winner: Entity,
+        factor: f32,
+    },
+}
+
+/// Friendly and hostile projectiles cancel each other on contact.
+pub struct SpellClash;
+
+impl SpellClash {
+    pub fn run(world: &mut World, space: &Space, audio: &mut Audio) {
+        let mut pairs = Vec::<(Entity, Entity)>::new();
+
+        for (entity_a, (collidable, damage_a)) in world
+            .query::<(&Collidable, &Damage)>()
+            .with::<(&Projectile, &Spell)>()
+            .iter()
+        {
+            if let Some(space_object) = collidable.space_object.as_ref() {
+                for object in space.collisions(space_object, true) {
+                    let Some(entity_b) = object.entity else {
+                        continue;
+                    };
+                    if entity_a.to_bits() >= entity_b.to_bits()
+                        || !world.satisfies::<&Projectile>(entity_b).unwrap_or_default()
+                    {
+                        continue;
+                    }
+                    let Ok(damage_b) = world.get::<&Damage>(entity_b) else {
+                        continue;
+                    };
+                    if Self::opposing(damage_a.layer, damage_b.layer) {
+                        pairs.push((entity_a, entity_b));
+                    }
+                }
+            }
+        }
+
+        let mut to_despawn = HashSet::<Entity>::new();
+
+        for (entity_a, entity_b) in pairs {
+            if to_despawn.contains(&entity_a) || to_despawn.contains(&entity_b) {
+                continue;
+            }
+            let Some(outcome) = Self::outcome(world, entity_a, entity_b) else {
+                continue;
+            };
+            let position = Self::midpoint(world, entity_a, entity_b);
+            match outcome {
+                ClashOutcome::Annihilate => {
+                    to_despawn.insert(entity_a);
+                    to_despawn.insert(entity_b);
+                }
+                ClashOutcome::Weaken {
+                    loser,
+                    winner,
+                    factor,
+                } => {
+                    to_despawn.insert(loser);
+                    if let Ok(mut damage) = world.get::<&mut Damage>(winner) {
+                        damage.value *= factor;
+                    }
+                }
+            }
+            Self::feedback(world, audio, position, outcome);
+        }
+
+        for entity in to_despawn {
+            let _ = world.despawn(entity);
+        }
+    }
+
+    /// Strength of spell against the other one: its damage, scaled by size
+    /// and doubled when its element counters the other element.
+    pub fn strength(damage: f32, spell: &Spell, other: &Spell) -> f32 {
+        let size = spell.size.radius() / SpellTagSize::Small.radius();
+        let counter = if spell.effect.counters(other.effect) {
+            COUNTER_MULTIPLIER
+        } else {
+            1.0
+        };
+        damage * size * counter
+    }
+
+    pub fn outcome(world: &World, entity_a: Entity, entity_b: Entity) -> Option<ClashOutcome> {
+        let mut query = world.query::<(&Spell, &Damage)>();
+        let view = query.view();
+        let (spell_a, damage_a) = view.get(entity_a)?;
+        let (spell_b, damage_b) = view.get(entity_b)?;
+        let strength_a = Self::strength(damage_a.value, spell_a, spell_b);
+        let strength_b = Self::strength(damage_b.value, spell_b, spell_a);
+        let stronger = strength_a.max(strength_b);
+
+        if stronger <= 0.0 || (strength_a - strength_b).abs() <= stronger * ANNIHILATION_MARGIN {
+            return Some(ClashOutcome::Annihilate);
+        }
+        let (loser, winner, weaker) = if strength_a < strength_b {
+            (entity_a, entity_b, strength_a)
+        } else {
+            (entity_b, entity_a, strength_b)
+        };
+        Some(ClashOutcome::Weaken {
+            loser,
+            winner,
+            factor: 1.0 - weaker / stronger,
+        })
+    }
+
+    // Friendly spells oppose spells of everyone else, so enemies do not
+    // cancel each other's spells.
+    fn opposing(layer_a: DamageLayer, layer_b: DamageLayer) -> bool {
+        (layer_a == DamageLayer::Player) != (layer_b == DamageLayer::Player)
+    }
+
+    fn midpoint(world: &World, entity_a: Entity, entity_b: Entity) -> Vec2<f32> {
+        let position = |entity| {
+            world
+                .get::<&Transform<f32, f32, f32>>(entity)
+                .map(|transform| transform.position.xy())
+                .unwrap_or_default()
+        };
+        (position(entity_a) + position(entity_b)) * 0.5
+    }
+
+    fn feedback(world: &mut World, audio: &mut Audio, position: Vec2<f32>, outcome: ClashOutcome) {
+        let (count, scale) = match outcome {
+            ClashOutcome::Annihilate => (40, 1.0..=2.5),
+            ClashOutcome::Weaken { .. } => (20, 0.5..=1.5),
+        };
+        for _ in 0..count {
+            world.spawn((Particle::new(
+                "particle/sparks".into(),
+                position,
+                Vec2::<f32>::zero(),
+                180.0f32.to_radians(),
+                150.0..=300.0,
+                0.2..=0.6,
+                scale.clone(),
+            ),));
+        }
+
+        if let Some(mut sound) = audio.play("sound/clash") {
+            let _ = sound.set_volume(0.6, Default::default());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{
+        systems::collision_detector::CollisionDetector,
+        utils::{magic::spell_tag::SpellTagEffect, space::SpaceCollider, test_utils::collidable},
+    };
+
+    fn spawn(
+        world: &mut World,
+        x: f32,
+        layer: DamageLayer,
+        effect: SpellTagEffect,
+        size: SpellTagSize,
+        damage: f32,
+    ) -> Entity {
+        world.spawn((
+            Transform::<f32, f32, f32> {
+                position: Vec2::new(x, 0.0).into(),
+                ..Default::default()
+            },
+            collidable(SpaceCollider::circle(10.0)),
+            Projectile::new(100.0, Vec2::unit_x(), 5.0),
+            Damage {
+                value: damage,
+                layer,
+            },
+            Spell {
+                effect,
+                size,
+                ..Spell::basic()
+            },
+        ))
+    }
+
+    #[test]
+    fn test_spells_clash() {
+        let mut world = World::new();
+        let mut space = Space::default();
+        let mut audio = Audio::default();
+        let player = DamageLayer::Player;
+        let enemy = DamageLayer::None;
+
+        // water puts out fire of the same power and keeps going weakened.
+        let water = spawn(
+            &mut world,
+            0.0,
+            player,
+            SpellTagEffect::Water,
+            SpellTagSize::Small,
+            5.0,
+        );
+        let fire = spawn(
+            &mut world,
+            15.0,
+            enemy,
+            SpellTagEffect::Fire,
+            SpellTagSize::Small,
+            5.0,
+        );
+        // bigger spell eats smaller one.
+        let big = spawn(
+            &mut world,
+            1000.0,
+            player,
+            SpellTagEffect::None,
+            SpellTagSize::Large,
+            5.0,
+        );
+        let small = spawn(
+            &mut world,
+            1015.0,
+            enemy,
+            SpellTagEffect::None,
+            SpellTagSize::Small,
+            5.0,
+        );
+        // equal spells annihilate.
+        let a = spawn(
+            &mut world,
+            2000.0,
+            player,
+            SpellTagEffect::None,
+            SpellTagSize::Medium,
+            5.0,
+        );
+        let b = spawn(
+            &mut world,
+            2015.0,
+            enemy,
+            SpellTagEffect::None,
+            SpellTagSize::Medium,
+            5.0,
+        );
+        // enemies do not cancel their own spells.
+        let c = spawn(
+            &mut world,
+            3000.0,
+            enemy,
+            SpellTagEffect::Water,
+            SpellTagSize::Small,
+            5.0,
+        );
+        let d = spawn(
+            &mut world,
+            3015.0,
+            enemy,
+            SpellTagEffect::Fire,
+            SpellTagSize::Small,
+            5.0,
+        );
+
+        CollisionDetector::run(&world, &mut space);
+        SpellClash::run(&mut world, &space, &mut audio);
+
+        assert!(world.contains(water) && !world.contains(fire));
+        assert_eq!(world.get::<&Damage>(water).unwrap().value, 2.5);
+        assert!(world.contains(big) && !world.contains(small));
+        assert_eq!(world.get::<&Damage>(big).unwrap().value, 3.75);
+        assert!(!world.contains(a) && !world.contains(b));
+        assert!(world.contains(c) && world.contains(d));
+        assert!(world.query::<&Particle>().iter().count() > 0);
+    }
+}
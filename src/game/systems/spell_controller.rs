@@ -61,7 +61,7 @@ impl SpellController {
             transform.scale = spell.size.scale().into();
 
             if let Some(space_object) = collidable.space_object.as_mut() {
-                space_object.collider_radius = spell.size.radius();
+                space_object.collider.set_radius(spell.size.radius());
             }
         }
     }
@@ -0,0 +1,296 @@
+use super::player_controller::PlayerCastAction;
+use crate::game::{
+    components::{
+        collidable::Collidable, enemy::Enemy, immobility::Immobility, spell::Spell, summon::Summon,
+    },
+    states::new_gameplay::NewGameplay,
+    utils::{
+        audio::Audio,
+        magic::spell_tag::{SpellTagDamage, SpellTagSize, SpellTagSpeed},
+        space::Space,
+    },
+};
+use hecs::{Entity, World};
+use micro_games_kit::third_party::vek::{Transform, Vec2};
+use std::collections::HashMap;
+
+/// Enemies further than this are not noticed by summons.
+const SEEK_RANGE: f32 = 700.0;
+/// Summons cast spells at enemies within this distance.
+const CAST_RANGE: f32 = 450.0;
+/// Summons without enemies around come back closer than this to their owner.
+const FOLLOW_DISTANCE: f32 = 150.0;
+
+pub struct SummonController;
+
+impl SummonController {
+    pub fn run(world: &mut World, space: &Space, audio: &mut Audio, delta_time: f32) {
+        let enemies = world
+            .query::<&Transform<f32, f32, f32>>()
+            .with::<&Enemy>()
+            .iter()
+            .map(|(_, transform)| transform.position.xy())
+            .collect::<Vec<_>>();
+        let owners = world
+            .query::<&Summon>()
+            .iter()
+            .filter_map(|(_, summon)| {
+                let transform = world.get::<&Transform<f32, f32, f32>>(summon.owner).ok()?;
+                Some((summon.owner, transform.position.xy()))
+            })
+            .collect::<HashMap<_, _>>();
+        let mut casts = Vec::<(Entity, PlayerCastAction)>::new();
+        let mut to_despawn = Vec::<Entity>::new();
+
+        for (entity, (summon, transform, immobility)) in world
+            .query::<(
+                &mut Summon,
+                &mut Transform<f32, f32, f32>,
+                Option<&Immobility>,
+            )>()
+            .iter()
+        {
+            summon.alive_time += delta_time;
+            summon.ttl -= delta_time;
+            summon.cooldown -= delta_time;
+            if summon.ttl <= 0.0 {
+                to_despawn.push(entity);
+                continue;
+            }
+            if immobility.is_some_and(|immobility| immobility.time_left > 0.0) {
+                continue;
+            }
+
+            let position = transform.position.xy();
+            let nearest = enemies
+                .iter()
+                .map(|enemy| (*enemy - position, enemy.distance(position)))
+                .filter(|(_, distance)| *distance <= SEEK_RANGE)
+                .min_by(|(_, a), (_, b)| a.total_cmp(b));
+
+            // seek enemies, otherwise stay around the owner.
+            let direction = match nearest {
+                Some((offset, distance)) if distance > summon.kind.reach() => {
+                    offset.try_normalized().unwrap_or_default()
+                }
+                Some(_) => Vec2::zero(),
+                None => owners
+                    .get(&summon.owner)
+                    .map(|owner| *owner - position)
+                    .filter(|offset| offset.magnitude() > FOLLOW_DISTANCE)
+                    .and_then(|offset| offset.try_normalized())
+                    .unwrap_or_default(),
+            };
+            transform.position += direction * summon.kind.speed() * delta_time;
+
+            if let Some((offset, distance)) = nearest {
+                if distance <= CAST_RANGE && summon.cooldown <= 0.0 {
+                    summon.cooldown = summon.kind.cooldown();
+                    casts.push((
+                        entity,
+                        PlayerCastAction {
+                            direction: offset.try_normalized().unwrap_or(Vec2::unit_x()),
+                            position,
+                            spell: Spell {
+                                effect: summon.effect,
+                                size: SpellTagSize::Small,
+                                speed: SpellTagSpeed::Fast,
+                                damage: SpellTagDamage::Medium,
+                                ..Spell::basic()
+                            },
+                        },
+                    ));
+                }
+            }
+        }
+
+        for entity in to_despawn {
+            let _ = world.despawn(entity);
+        }
+
+        Self::body_block(world, space);
+
+        for (entity, cast) in casts {
+            let entities = NewGameplay::cast_spell(world, audio, cast, entity);
+            NewGameplay::befriend(world, &entities);
+        }
+    }
+
+    /// Sends oldest summons of owner away so a new one fits within the limit.
+    pub fn make_room(world: &mut World, owner: Entity) {
+        let mut summons = world
+            .query::<&Summon>()
+            .iter()
+            .filter(|(_, summon)| summon.owner == owner)
+            .map(|(entity, summon)| (entity, summon.alive_time))
+            .collect::<Vec<_>>();
+        summons.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+        let excess = (summons.len() + 1).saturating_sub(Summon::LIMIT);
+        for (entity, _) in summons.into_iter().take(excess) {
+            let _ = world.despawn(entity);
+        }
+    }
+
+    // Pushes enemies out of summons, so they stand in the way.
+    fn body_block(world: &World, space: &Space) {
+        let mut pushes = Vec::<(Entity, Vec2<f32>)>::new();
+
+        for (_, (collidable, transform)) in world
+            .query::<(&Collidable, &Transform<f32, f32, f32>)>()
+            .with::<&Summon>()
+            .iter()
+        {
+            let Some(mut object) = collidable.space_object.clone() else {
+                continue;
+            };
+            object.position = transform.position.xy();
+            for other in space.collisions(&object, true) {
+                let Some(entity) = other.entity else {
+                    continue;
+                };
+                if !world.satisfies::<&Enemy>(entity).unwrap_or_default() {
+                    continue;
+                }
+                if let Some(push) = other.contact(&object) {
+                    pushes.push((entity, push));
+                }
+            }
+        }
+
+        for (entity, push) in pushes {
+            if let Ok(mut transform) = world.get::<&mut Transform<f32, f32, f32>>(entity) {
+                transform.position += push;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{
+        components::{
+            damage::{Damage, DamageLayer},
+            health::Health,
+            projectile::Projectile,
+        },
+        systems::collision_detector::CollisionDetector,
+        utils::{
+            magic::spell_tag::{SpellTagDuration, SpellTagEffect, SpellTagSummon},
+            test_utils::player,
+        },
+    };
+
+    #[test]
+    fn test_summons() {
+        let mut world = World::new();
+        let mut space = Space::default();
+        let mut audio = Audio::default();
+        let player = world.spawn((
+            player(),
+            Transform::<f32, f32, f32>::default(),
+            Health {
+                value: 100.0,
+                limit: 100.0,
+                layer: DamageLayer::Player,
+            },
+        ));
+        let enemy = world.spawn((
+            Enemy::default(),
+            Transform::<f32, f32, f32> {
+                position: Vec2::new(600.0, 0.0).into(),
+                ..Default::default()
+            },
+        ));
+        let cast = || PlayerCastAction {
+            direction: Vec2::unit_x(),
+            position: Vec2::zero(),
+            spell: Spell {
+                effect: SpellTagEffect::Fire,
+                duration: SpellTagDuration::Medium,
+                summon: Some(SpellTagSummon::Familiar),
+                ..Spell::basic()
+            },
+        };
+        let summons = |world: &World| world.query::<&Summon>().iter().count();
+
+        // only player summons, up to the limit.
+        for _ in 0..4 {
+            NewGameplay::cast_spell(&mut world, &mut audio, cast(), player);
+        }
+        assert_eq!(summons(&world), Summon::LIMIT);
+        let projectiles = NewGameplay::cast_spell(&mut world, &mut audio, cast(), enemy);
+        assert_eq!(summons(&world), Summon::LIMIT);
+        for projectile in projectiles {
+            assert!(world.satisfies::<&Projectile>(projectile).unwrap());
+            world.despawn(projectile).unwrap();
+        }
+
+        // they go for the enemy, stop within reach and cast friendly spells.
+        for _ in 0..120 {
+            CollisionDetector::run(&world, &mut space);
+            SummonController::run(&mut world, &space, &mut audio, 1.0 / 60.0);
+        }
+        for (_, transform) in world
+            .query::<&Transform<f32, f32, f32>>()
+            .with::<&Summon>()
+            .iter()
+        {
+            let distance = transform.position.xy().distance(Vec2::new(600.0, 0.0));
+            assert!(
+                distance <= SpellTagSummon::Familiar.reach() + 5.0,
+                "{}",
+                distance
+            );
+        }
+        let layers = world
+            .query::<&Damage>()
+            .with::<&Projectile>()
+            .iter()
+            .map(|(_, damage)| damage.layer)
+            .collect::<Vec<_>>();
+        assert!(!layers.is_empty());
+        assert!(layers.iter().all(|layer| *layer == DamageLayer::Player));
+        assert!(!DamageLayer::Player.allow_damage(DamageLayer::Player));
+
+        // without enemies they come back to the player, until they are gone.
+        world.despawn(enemy).unwrap();
+        for _ in 0..120 {
+            SummonController::run(&mut world, &space, &mut audio, 1.0 / 60.0);
+        }
+        for (_, transform) in world
+            .query::<&Transform<f32, f32, f32>>()
+            .with::<&Summon>()
+            .iter()
+        {
+            assert!(transform.position.xy().magnitude() <= 400.0);
+        }
+        SummonController::run(&mut world, &space, &mut audio, 10.0);
+        assert_eq!(summons(&world), 0);
+    }
+
+    #[test]
+    fn test_make_room_sends_oldest_away() {
+        let mut world = World::new();
+        let owner = world.spawn(());
+        let mut summon = |alive_time: f32, ttl: f32| {
+            world.spawn((Summon {
+                kind: SpellTagSummon::Minion,
+                owner,
+                effect: SpellTagEffect::None,
+                cooldown: 0.0,
+                alive_time,
+                ttl,
+            },))
+        };
+        // longer lasting summon is still the oldest one.
+        let oldest = summon(9.0, 20.0);
+        let older = summon(5.0, 1.0);
+        let newest = summon(1.0, 2.0);
+
+        SummonController::make_room(&mut world, owner);
+        assert!(!world.contains(oldest));
+        assert!(world.contains(older));
+        assert!(world.contains(newest));
+    }
+}
@@ -0,0 +1,232 @@
+use crate::game::{
+    components::{
+        collidable::Collidable, damage::Damage, health::Health,
+        particle_generator::ParticleGenerator, wall::Wall,
+    },
+    systems::{damage_dealer::DamageDealer, zone_controller::ZoneController},
+    utils::{
+        magic::spell_tag::SpellTagEffect,
+        space::{Space, SpaceCollider, SpaceObject},
+    },
+};
+use hecs::{Entity, World};
+use micro_games_kit::third_party::vek::Transform;
+
+/// How far from wall surface things still count as touching it, since
+/// obstacle collision keeps them just outside.
+const TOUCH_MARGIN: f32 = 5.0;
+
+pub struct WallController;
+
+impl WallController {
+    pub fn run(world: &mut World, space: &Space, delta_time: f32) {
+        let mut touches = Vec::<(Entity, Wall, SpaceObject)>::new();
+        let mut to_despawn = Vec::<Entity>::new();
+
+        for (entity, (wall, collidable, transform, health, generator)) in world
+            .query::<(
+                &mut Wall,
+                &Collidable,
+                &Transform<f32, f32, f32>,
+                Option<&Health>,
+                Option<&mut ParticleGenerator>,
+            )>()
+            .iter()
+        {
+            wall.ttl -= delta_time;
+            wall.time_accumulator += delta_time;
+            if wall.ttl <= 0.0 {
+                to_despawn.push(entity);
+                continue;
+            }
+
+            // cracked walls crumble, emitting less particles.
+            if let (Some(health), Some(generator)) = (health, generator) {
+                let integrity = (health.value / health.limit).clamp(0.0, 1.0);
+                generator.batch_size =
+                    ((Wall::PARTICLES as f32 * integrity).ceil() as usize).max(1);
+            }
+
+            if wall.time_accumulator >= wall.interval {
+                wall.time_accumulator -= wall.interval;
+                if let Some(mut object) = collidable.space_object.clone() {
+                    object.entity = None;
+                    object.position = transform.position.xy();
+                    object.collider = Self::thickened(object.collider);
+                    touches.push((entity, *wall, object));
+                }
+            }
+        }
+
+        for (entity, wall, probe) in touches {
+            let targets = space
+                .collisions(&probe, true)
+                .filter_map(|object| object.entity)
+                .filter(|target| *target != entity && *target != wall.caster)
+                .filter(|target| !world.satisfies::<&Wall>(*target).unwrap_or_default())
+                .filter(|target| {
+                    world
+                        .get::<&Health>(*target)
+                        .is_ok_and(|health| health.layer != wall.layer)
+                })
+                .collect::<Vec<_>>();
+            for target in targets {
+                Self::touch(world, entity, &wall, target);
+            }
+        }
+
+        for entity in to_despawn {
+            let _ = world.despawn(entity);
+        }
+    }
+
+    fn thickened(collider: SpaceCollider) -> SpaceCollider {
+        match collider {
+            SpaceCollider::Circle { radius } => SpaceCollider::Circle {
+                radius: radius + TOUCH_MARGIN,
+            },
+            SpaceCollider::Capsule {
+                radius,
+                half_length,
+                rotation,
+            } => SpaceCollider::Capsule {
+                radius: radius + TOUCH_MARGIN,
+                half_length,
+                rotation,
+            },
+            SpaceCollider::Box {
+                half_extents,
+                rotation,
+            } => SpaceCollider::Box {
+                half_extents: half_extents + TOUCH_MARGIN,
+                rotation,
+            },
+        }
+    }
+
+    // Water walls freeze and electric walls zap whatever touches them, on top
+    // of applying their element. Things of wall's side are left alone.
+    fn touch(world: &World, entity: Entity, wall: &Wall, target: Entity) {
+        ZoneController::apply_element(world, wall.effect, target);
+        match wall.effect {
+            SpellTagEffect::Water => ZoneController::immobilize(world, target, Wall::FREEZE_TIME),
+            SpellTagEffect::Electric => {
+                DamageDealer::deal(
+                    world,
+                    entity,
+                    Damage {
+                        value: Wall::ZAP_DAMAGE,
+                        layer: wall.layer,
+                    },
+                    target,
+                );
+            }
+            SpellTagEffect::Fire | SpellTagEffect::None => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{
+        components::{damage::DamageLayer, obstacle::Obstacle, projectile::Projectile},
+        systems::{
+            collision_detector::CollisionDetector, projectile_controller::ProjectileController,
+        },
+        utils::test_utils::collidable,
+    };
+    use micro_games_kit::third_party::vek::Vec2;
+
+    #[test]
+    fn test_walls_block_and_zap() {
+        let mut world = World::new();
+        let mut space = Space::default();
+        let caster = world.spawn((
+            Transform::<f32, f32, f32> {
+                position: Vec2::new(-100.0, 0.0).into(),
+                ..Default::default()
+            },
+            Health {
+                value: 50.0,
+                limit: 50.0,
+                layer: DamageLayer::Enemy,
+            },
+        ));
+        let wall = world.spawn((
+            Wall {
+                layer: DamageLayer::Enemy,
+                ..Wall::new(SpellTagEffect::Electric, caster, 2.0)
+            },
+            Obstacle::default(),
+            Health {
+                value: 20.0,
+                limit: 20.0,
+                layer: DamageLayer::Enemy,
+            },
+            Transform::<f32, f32, f32>::default(),
+            collidable(SpaceCollider::capsule(
+                Wall::THICKNESS,
+                100.0,
+                Vec2::unit_y(),
+            )),
+        ));
+        let projectile = |world: &mut World, y: f32, caster: Option<Entity>, layer: DamageLayer| {
+            world.spawn((
+                Transform::<f32, f32, f32> {
+                    position: Vec2::new(-20.0, y).into(),
+                    ..Default::default()
+                },
+                collidable(SpaceCollider::circle(10.0)),
+                Projectile {
+                    caster,
+                    ..Projectile::new(100.0, Vec2::unit_x(), 5.0)
+                },
+                Damage { value: 5.0, layer },
+            ))
+        };
+        let own = projectile(&mut world, -50.0, Some(caster), DamageLayer::None);
+        let friendly = projectile(&mut world, 0.0, None, DamageLayer::Enemy);
+        let hostile = projectile(&mut world, 50.0, None, DamageLayer::None);
+        let body = |world: &mut World, y: f32, layer: DamageLayer| {
+            world.spawn((
+                Transform::<f32, f32, f32> {
+                    position: Vec2::new(25.0, y).into(),
+                    ..Default::default()
+                },
+                collidable(SpaceCollider::circle(10.0)),
+                Health {
+                    value: 50.0,
+                    limit: 50.0,
+                    layer,
+                },
+            ))
+        };
+        let foe = body(&mut world, 0.0, DamageLayer::None);
+        let friend = body(&mut world, 60.0, DamageLayer::Enemy);
+
+        // own and friendly projectiles pass unharmed, hostile one is stopped
+        // and cracks the wall both on touch and when stopped.
+        CollisionDetector::run(&world, &mut space);
+        DamageDealer::run(&world, &space);
+        ProjectileController::run(&mut world, &space, 0.01);
+        assert!(world.contains(own));
+        assert!(world.contains(friendly));
+        assert!(!world.contains(hostile));
+        assert_eq!(world.get::<&Health>(wall).unwrap().value, 10.0);
+
+        // whatever of other side touches electric wall gets zapped every
+        // interval.
+        CollisionDetector::run(&world, &mut space);
+        WallController::run(&mut world, &space, 0.25);
+        assert_eq!(world.get::<&Health>(foe).unwrap().value, 50.0);
+        WallController::run(&mut world, &space, 0.25);
+        assert_eq!(world.get::<&Health>(foe).unwrap().value, 45.0);
+        assert_eq!(world.get::<&Health>(friend).unwrap().value, 50.0);
+        assert!(Wall::passes(&world, wall, friend));
+        assert!(!Wall::passes(&world, wall, foe));
+
+        WallController::run(&mut world, &space, 2.0);
+        assert!(!world.contains(wall));
+    }
+}
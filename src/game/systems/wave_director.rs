@@ -0,0 +1,218 @@
+use super::enemy_spawn::EnemySpawn;
+use crate::game::{
+    components::enemy::Enemy,
+    utils::{
+        enemies::EnemyDatabase,
+        space::Space,
+        waves::{WaveData, WaveScript},
+    },
+};
+use hecs::World;
+
+pub struct WaveDirector {
+    pub script: WaveScript,
+    pub enemy_spawn: EnemySpawn,
+    pub announcement_duration: f32,
+    wave_index: Option<usize>,
+    wave: Option<WaveData>,
+    wave_started_at: f32,
+    announcement_time: f32,
+    pending_boss: Option<String>,
+}
+
+impl WaveDirector {
+    pub fn new(script: WaveScript, enemy_spawn: EnemySpawn) -> Self {
+        Self {
+            script,
+            enemy_spawn,
+            announcement_duration: 3.0,
+            wave_index: None,
+            wave: None,
+            wave_started_at: 0.0,
+            announcement_time: 0.0,
+            pending_boss: None,
+        }
+    }
+
+    /// 1-based number of current wave, 0 before first wave started.
+    pub fn wave_number(&self) -> usize {
+        self.wave_index.map(|index| index + 1).unwrap_or_default()
+    }
+
+    /// Announcement text with its opacity, while it should be shown.
+    pub fn announcement(&self) -> Option<(String, f32)> {
+        if self.announcement_time > 0.0 {
+            let alpha = (self.announcement_time / self.announcement_duration * 3.0).min(1.0);
+            Some((format!("Wave {}", self.wave_number()), alpha))
+        } else {
+            None
+        }
+    }
+
+    pub fn run(
+        &mut self,
+        world: &mut World,
+        space: &Space,
+        database: &EnemyDatabase,
+        alive_time_seconds: f32,
+        delta_time: f32,
+    ) {
+        self.announcement_time = (self.announcement_time - delta_time).max(0.0);
+
+        let finished = match self.wave.as_ref() {
+            Some(wave) => {
+                let timed_out = alive_time_seconds - self.wave_started_at >= wave.duration;
+                let cleared = self.enemy_spawn.spawned >= wave.total
+                    && self.pending_boss.is_none()
+                    && world.query::<&Enemy>().iter().next().is_none();
+                timed_out || cleared
+            }
+            None => true,
+        };
+        if finished {
+            self.start_wave(
+                self.wave_index.map(|index| index + 1).unwrap_or_default(),
+                alive_time_seconds,
+            );
+        }
+
+        if let Some(kind) = self.pending_boss.as_deref() {
+            if self
+                .enemy_spawn
+                .spawn_kind(world, space, database, kind)
+                .is_some()
+            {
+                self.pending_boss = None;
+            }
+        }
+
+        self.enemy_spawn
+            .run(world, space, database, alive_time_seconds, delta_time);
+    }
+
+    fn start_wave(&mut self, index: usize, alive_time_seconds: f32) {
+        let Some(wave) = self.script.wave(index) else {
+            return;
+        };
+        self.enemy_spawn.interval_seconds = wave.spawn_interval;
+        self.enemy_spawn.count_limit = wave.count_limit;
+        self.enemy_spawn.spawn_limit = Some(wave.total);
+        self.enemy_spawn.kinds = wave
+            .enemies
+            .iter()
+            .map(|(kind, weight)| (kind.to_owned(), *weight))
+            .collect();
+        self.enemy_spawn.effects = wave.elements.to_weights();
+        self.enemy_spawn.spawned = 0;
+        self.pending_boss = wave.boss.clone();
+        self.wave_index = Some(index);
+        self.wave = Some(wave);
+        self.wave_started_at = alive_time_seconds;
+        self.announcement_time = self.announcement_duration;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{components::boss::Boss, utils::test_utils::player};
+    use micro_games_kit::third_party::vek::Transform;
+
+    const SCRIPT: &str = r#"
+        [[waves]]
+        duration = 10.0
+        spawn_interval = 1.0
+        count_limit = 2
+        total = 2
+        enemies = { slime = 1 }
+        elements = { none = 1 }
+
+        [[waves]]
+        duration = 20.0
+        spawn_interval = 0.5
+        count_limit = 5
+        total = 5
+        enemies = { slime = 1, truck = 1 }
+        elements = { fire = 1, water = 1 }
+        boss = "slime_king"
+
+        [endless]
+        spawn_interval_multiplier = 0.5
+        spawn_interval_min = 0.2
+        count_limit_increment = 1
+        total_increment = 2
+        duration_increment = 5.0
+        boss_interval = 2
+    "#;
+
+    #[test]
+    fn test_wave_script() {
+        let script = WaveScript::load_from_str(SCRIPT).unwrap();
+        assert_eq!(script.wave(0), Some(script.waves[0].clone()));
+        assert_eq!(script.wave(1), Some(script.waves[1].clone()));
+
+        let wave = script.wave(2).unwrap();
+        assert_eq!(wave.duration, 25.0);
+        assert_eq!(wave.spawn_interval, 0.25);
+        assert_eq!(wave.count_limit, 6);
+        assert_eq!(wave.total, 7);
+        assert_eq!(wave.boss, None);
+
+        let wave = script.wave(3).unwrap();
+        assert_eq!(wave.spawn_interval, 0.2);
+        assert_eq!(wave.count_limit, 7);
+        assert_eq!(wave.boss.as_deref(), Some("slime_king"));
+
+        assert!(!WaveScript::load().waves.is_empty());
+    }
+
+    #[test]
+    fn test_wave_director() {
+        let mut world = World::new();
+        world.spawn((player(), Transform::<f32, f32, f32>::default()));
+        let mut director = WaveDirector::new(
+            WaveScript::load_from_str(SCRIPT).unwrap(),
+            EnemySpawn::new(1000.0, 1.0, 1),
+        );
+        let space = Space::default();
+        let database = EnemyDatabase::load();
+        let delta_time = 0.1;
+        let mut time = 0.0;
+
+        director.run(&mut world, &space, &database, time, delta_time);
+        assert_eq!(director.wave_number(), 1);
+        assert_eq!(director.announcement().unwrap().0, "Wave 1");
+
+        // wave 1 spawns its total and no more.
+        for _ in 0..50 {
+            time += delta_time;
+            director.run(&mut world, &space, &database, time, delta_time);
+        }
+        assert_eq!(director.wave_number(), 1);
+        assert_eq!(world.query::<&Enemy>().iter().count(), 2);
+        assert!(director.announcement().is_none());
+
+        // clearing all enemies advances to next wave.
+        let enemies = world
+            .query::<&Enemy>()
+            .iter()
+            .map(|(entity, _)| entity)
+            .collect::<Vec<_>>();
+        for entity in enemies {
+            world.despawn(entity).unwrap();
+        }
+        time += delta_time;
+        director.run(&mut world, &space, &database, time, delta_time);
+        assert_eq!(director.wave_number(), 2);
+        assert_eq!(director.enemy_spawn.count_limit, 5);
+        // wave 2 brings its boss along.
+        assert_eq!(world.query::<&Boss>().iter().count(), 1);
+
+        // running out of time advances too, past the script into endless waves.
+        time += 20.0;
+        director.run(&mut world, &space, &database, time, delta_time);
+        assert_eq!(director.wave_number(), 3);
+        assert_eq!(director.enemy_spawn.count_limit, 6);
+        assert_eq!(director.enemy_spawn.spawn_limit, Some(7));
+    }
+}
@@ -0,0 +1,290 @@
+use crate::game::{
+    components::{
+        damage::Damage,
+        effect::{Effect, EffectReaction},
+        health::Health,
+        immobility::Immobility,
+        zone::Zone,
+    },
+    systems::damage_dealer::DamageDealer,
+    utils::{
+        magic::spell_tag::SpellTagEffect,
+        space::{Space, SpaceCollider, SpaceObject},
+    },
+};
+use hecs::{Entity, World};
+use micro_games_kit::third_party::vek::{Transform, Vec2};
+use std::collections::HashSet;
+
+pub struct ZoneController;
+
+impl ZoneController {
+    pub fn run(world: &mut World, space: &Space, delta_time: f32) {
+        Self::combine(world);
+
+        let mut ticks = Vec::<(Entity, Vec2<f32>, Zone)>::new();
+        let mut to_despawn = Vec::<Entity>::new();
+
+        for (entity, (zone, transform)) in world
+            .query::<(&mut Zone, &Transform<f32, f32, f32>)>()
+            .iter()
+        {
+            zone.ttl -= delta_time;
+            zone.time_accumulator += delta_time;
+            if zone.time_accumulator >= zone.interval {
+                zone.time_accumulator -= zone.interval;
+                ticks.push((entity, transform.position.xy(), zone.clone()));
+            }
+            if zone.ttl <= 0.0 {
+                to_despawn.push(entity);
+            }
+        }
+
+        for (entity, position, zone) in ticks {
+            let probe = SpaceObject {
+                entity: None,
+                position,
+                collider: SpaceCollider::circle(zone.radius),
+            };
+            let targets = space
+                .collisions(&probe, true)
+                .filter_map(|object| object.entity)
+                .filter(|target| *target != entity && Some(*target) != zone.caster)
+                .collect::<Vec<_>>();
+            for target in targets {
+                Self::affect(world, entity, &zone, target);
+            }
+        }
+
+        for entity in to_despawn {
+            let _ = world.despawn(entity);
+        }
+    }
+
+    /// Replaces overlapping zones of reacting elements with their combination.
+    fn combine(world: &mut World) {
+        let zones = world
+            .query::<(&Zone, &Transform<f32, f32, f32>)>()
+            .iter()
+            .map(|(entity, (zone, transform))| (entity, transform.position.xy(), zone.clone()))
+            .collect::<Vec<_>>();
+        let mut used = HashSet::<Entity>::new();
+        let mut to_spawn = Vec::<(Vec2<f32>, Zone)>::new();
+
+        for (index, (entity_a, position_a, zone_a)) in zones.iter().enumerate() {
+            if used.contains(entity_a) {
+                continue;
+            }
+            for (entity_b, position_b, zone_b) in zones.iter().skip(index + 1) {
+                if used.contains(entity_b) || !zone_a.overlaps(*position_a, zone_b, *position_b) {
+                    continue;
+                }
+                if let Some(zone) = zone_a.combine(zone_b) {
+                    used.insert(*entity_a);
+                    used.insert(*entity_b);
+                    let position = (*position_a * zone_a.radius + *position_b * zone_b.radius)
+                        / (zone_a.radius + zone_b.radius);
+                    to_spawn.push((position, zone));
+                    break;
+                }
+            }
+        }
+
+        for entity in used {
+            let _ = world.despawn(entity);
+        }
+        for (position, zone) in to_spawn {
+            world.spawn((
+                Transform::<f32, f32, f32> {
+                    position: position.into(),
+                    ..Default::default()
+                },
+                zone,
+            ));
+        }
+    }
+
+    fn affect(world: &World, source: Entity, zone: &Zone, target: Entity) {
+        // friendly zones leave friends alone.
+        let allowed = world
+            .get::<&Health>(target)
+            .map(|health| zone.layer.allow_damage(health.layer))
+            .unwrap_or_default();
+        if !allowed {
+            return;
+        }
+
+        if zone.damage > 0.0 {
+            DamageDealer::deal(
+                world,
+                source,
+                Damage {
+                    value: zone.damage,
+                    layer: zone.layer,
+                },
+                target,
+            );
+        }
+
+        Self::apply_element(world, zone.effect, target);
+        Self::immobilize(world, target, zone.reaction.immobile_time());
+    }
+
+    /// Reacts element with effect of the target, which takes damage of the
+    /// reaction and gets immobilized by it.
+    pub fn apply_element(world: &World, effect: SpellTagEffect, target: Entity) -> EffectReaction {
+        if effect == SpellTagEffect::None {
+            return EffectReaction::None;
+        }
+        let reaction = match world.get::<&mut Effect>(target) {
+            Ok(mut target_effect) => target_effect.react(&mut Effect::from(effect)),
+            Err(_) => return EffectReaction::None,
+        };
+        if let Ok(mut health) = world.get::<&mut Health>(target) {
+            health.value = (health.value - reaction.damage()).max(0.0);
+        }
+        Self::immobilize(world, target, reaction.immobile_time());
+        reaction
+    }
+
+    /// Stops target for given time, unless it is already immobile.
+    pub fn immobilize(world: &World, target: Entity, time: f32) {
+        if time <= 0.0 {
+            return;
+        }
+        if let Ok(mut immobility) = world.get::<&mut Immobility>(target) {
+            if immobility.time_left <= 0.0 {
+                immobility.time_left = time;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{
+        components::damage::DamageLayer, systems::collision_detector::CollisionDetector,
+        utils::test_utils::collidable,
+    };
+
+    fn spawn_zone(world: &mut World, x: f32, zone: Zone) -> Entity {
+        world.spawn((
+            Transform::<f32, f32, f32> {
+                position: Vec2::new(x, 0.0).into(),
+                ..Default::default()
+            },
+            zone,
+        ))
+    }
+
+    #[test]
+    fn test_zone_ticks() {
+        let mut world = World::new();
+        let mut space = Space::default();
+        let target = world.spawn((
+            Transform::<f32, f32, f32>::default(),
+            collidable(SpaceCollider::circle(10.0)),
+            Health {
+                value: 50.0,
+                limit: 50.0,
+                layer: DamageLayer::None,
+            },
+            Effect::from(SpellTagEffect::Water),
+            Immobility::default(),
+        ));
+        let zone = spawn_zone(
+            &mut world,
+            30.0,
+            Zone::new(SpellTagEffect::Electric, 25.0, 5.0, 1.2),
+        );
+        CollisionDetector::run(&world, &mut space);
+
+        // first tick right away: damage, then water and electricity paralize.
+        ZoneController::run(&mut world, &space, 0.1);
+        assert_eq!(world.get::<&Health>(target).unwrap().value, 45.0);
+        assert_eq!(world.get::<&Immobility>(target).unwrap().time_left, 3.0);
+        assert_eq!(*world.get::<&Effect>(target).unwrap(), Effect::default());
+
+        // nothing until the next tick.
+        ZoneController::run(&mut world, &space, 0.1);
+        assert_eq!(world.get::<&Health>(target).unwrap().value, 45.0);
+        ZoneController::run(&mut world, &space, 0.4);
+        assert_eq!(world.get::<&Health>(target).unwrap().value, 40.0);
+
+        ZoneController::run(&mut world, &space, 0.6);
+        assert!(!world.contains(zone));
+    }
+
+    #[test]
+    fn test_zones_react() {
+        let mut world = World::new();
+        let space = Space::default();
+        let fire = spawn_zone(
+            &mut world,
+            0.0,
+            Zone::new(SpellTagEffect::Fire, 50.0, 1.0, 5.0),
+        );
+        let water = spawn_zone(
+            &mut world,
+            80.0,
+            Zone::new(SpellTagEffect::Water, 50.0, 1.0, 5.0),
+        );
+        let far = spawn_zone(
+            &mut world,
+            500.0,
+            Zone::new(SpellTagEffect::Electric, 50.0, 1.0, 5.0),
+        );
+
+        ZoneController::run(&mut world, &space, 0.1);
+        assert!(!world.contains(fire) && !world.contains(water) && world.contains(far));
+        let steam = world
+            .query::<(&Zone, &Transform<f32, f32, f32>)>()
+            .iter()
+            .find(|(_, (zone, _))| zone.reaction == EffectReaction::Steam)
+            .map(|(_, (zone, transform))| (zone.radius, transform.position.x))
+            .unwrap();
+        assert_eq!(steam, (75.0, 40.0));
+    }
+
+    #[test]
+    fn test_zones_of_one_side_react_into_zone_of_that_side() {
+        let mut world = World::new();
+        let mut space = Space::default();
+        let body = |world: &mut World, x: f32, layer: DamageLayer| {
+            world.spawn((
+                Transform::<f32, f32, f32> {
+                    position: Vec2::new(x, 0.0).into(),
+                    ..Default::default()
+                },
+                collidable(SpaceCollider::circle(10.0)),
+                Health {
+                    value: 50.0,
+                    limit: 50.0,
+                    layer,
+                },
+            ))
+        };
+        let player = body(&mut world, 0.0, DamageLayer::None);
+        let enemy = body(&mut world, 40.0, DamageLayer::Enemy);
+        for (x, effect) in [
+            (0.0, SpellTagEffect::Fire),
+            (40.0, SpellTagEffect::Electric),
+        ] {
+            spawn_zone(
+                &mut world,
+                x,
+                Zone {
+                    layer: DamageLayer::Enemy,
+                    ..Zone::new(effect, 50.0, 1.0, 5.0)
+                },
+            );
+        }
+        CollisionDetector::run(&world, &mut space);
+
+        // explosion of enemy zones hurts only the player.
+        ZoneController::run(&mut world, &space, 0.1);
+        assert!(world.get::<&Health>(player).unwrap().value < 50.0);
+        assert_eq!(world.get::<&Health>(enemy).unwrap().value, 50.0);
+    }
+}
@@ -0,0 +1,37 @@
+use crate::game::components::zone::Zone;
+use hecs::World;
+use micro_games_kit::{
+    context::GameContext,
+    third_party::{
+        spitfire_draw::{
+            sprite::{Sprite, SpriteTexture},
+            utils::{Drawable, TextureRef},
+        },
+        vek::{Rgba, Transform, Vec2, Vec3},
+    },
+};
+
+/// Size of zone decal images in pixels.
+const DECAL_SIZE: f32 = 128.0;
+/// Seconds it takes zone decal to fade away at the end of its life.
+const FADE_TIME: f32 = 0.5;
+
+pub struct ZoneRenderer;
+
+impl ZoneRenderer {
+    pub fn run(world: &World, context: &mut GameContext) {
+        for (_, (transform, zone)) in world.query::<(&Transform<f32, f32, f32>, &Zone)>().iter() {
+            let mut transform = *transform;
+            transform.scale = Vec3::broadcast(zone.radius * 2.0 / DECAL_SIZE);
+            let alpha = (zone.ttl / FADE_TIME).clamp(0.0, 1.0) * 0.75;
+            Sprite::single(SpriteTexture::new(
+                "u_image".into(),
+                TextureRef::name(zone.texture()),
+            ))
+            .transform(transform)
+            .pivot(Vec2::broadcast(0.5))
+            .tint(Rgba::new(1.0, 1.0, 1.0, alpha))
+            .draw(context.draw, context.graphics);
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use crate::game::components::buff::Buff;
+use micro_games_kit::third_party::raui_immediate_widgets::core::{
+    containers::content_box, image_box, text_box, Color, ContentBoxItemLayout, ImageBoxImage,
+    ImageBoxMaterial, ImageBoxProps, Rect, TextBoxFont, TextBoxHorizontalAlign, TextBoxProps,
+    TextBoxVerticalAlign,
+};
+
+const ICON_SIZE: f32 = 64.0;
+const SPACING: f32 = 80.0;
+
+/// Row of active buffs in top left corner, each with seconds it has left.
+pub fn buff_icons(buffs: &[Buff]) {
+    for (index, buff) in buffs.iter().enumerate() {
+        let left = 20.0 + index as f32 * SPACING;
+
+        content_box(
+            ContentBoxItemLayout {
+                anchors: 0.0.into(),
+                margin: Rect {
+                    left,
+                    right: -(left + ICON_SIZE),
+                    top: 20.0,
+                    bottom: -(20.0 + ICON_SIZE + 30.0),
+                },
+                ..Default::default()
+            },
+            || {
+                image_box((
+                    ContentBoxItemLayout {
+                        margin: Rect {
+                            bottom: 30.0,
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    },
+                    ImageBoxProps {
+                        material: ImageBoxMaterial::Image(ImageBoxImage {
+                            id: buff.kind.icon().to_owned(),
+                            ..Default::default()
+                        }),
+                        ..Default::default()
+                    },
+                ));
+
+                text_box((
+                    ContentBoxItemLayout {
+                        anchors: Rect {
+                            left: 0.0,
+                            right: 1.0,
+                            top: 1.0,
+                            bottom: 1.0,
+                        },
+                        margin: Rect {
+                            top: -30.0,
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    },
+                    TextBoxProps {
+                        text: format!("{:.0}s", buff.time_left.ceil()),
+                        horizontal_align: TextBoxHorizontalAlign::Center,
+                        vertical_align: TextBoxVerticalAlign::Middle,
+                        font: TextBoxFont {
+                            name: "roboto".to_owned(),
+                            size: 24.0,
+                        },
+                        color: Color {
+                            r: 1.0,
+                            g: 1.0,
+                            b: 1.0,
+                            a: 1.0,
+                        },
+                        ..Default::default()
+                    },
+                ));
+            },
+        );
+    }
+}
@@ -1,5 +1,7 @@
+pub mod buff_icons;
 pub mod health_bar;
 pub mod text_button;
+pub mod word_picker;
 
 use super::utils::world_to_screen_anchor;
 use micro_games_kit::{
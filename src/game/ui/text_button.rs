@@ -10,7 +10,11 @@ use micro_games_kit::third_party::{
     },
 };
 
-pub fn text_button(props: impl Into<Props>, message: impl ToString) -> ImmediateButton {
+pub fn text_button(
+    props: impl Into<Props>,
+    message: impl ToString,
+    audio: &mut Audio,
+) -> ImmediateButton {
     let result = button_paper(props.into().with(NavItemActive), |_| {
         text_paper((
             ContentBoxItemLayout {
@@ -26,10 +30,10 @@ pub fn text_button(props: impl Into<Props>, message: impl ToString) -> Immediate
         ));
     });
     if result.select_start() {
-        let _ = Audio::write().write().unwrap().play("button/select");
+        let _ = audio.play("button/select");
     }
     if result.trigger_start() {
-        let _ = Audio::write().write().unwrap().play("button/click");
+        let _ = audio.play("button/click");
     }
     result
 }
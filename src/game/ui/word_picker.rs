@@ -0,0 +1,96 @@
+use crate::game::systems::player_controller::WordPicker;
+use micro_games_kit::third_party::raui_immediate_widgets::core::{
+    containers::content_box, image_box, text_box, Color, ContentBoxItemLayout, ImageBoxFrame,
+    ImageBoxImage, ImageBoxImageScaling, ImageBoxMaterial, ImageBoxProps, Rect, TextBoxFont,
+    TextBoxHorizontalAlign, TextBoxProps, TextBoxVerticalAlign,
+};
+
+const RADIUS: f32 = 220.0;
+const SLOT_HALF_SIZE: (f32, f32) = (90.0, 22.0);
+
+/// Ring of words around screen center, where the player stands.
+pub fn word_picker(group: &str, words: &[String], selected: Option<usize>) {
+    label(
+        (0.0, 0.0),
+        (110.0, 26.0),
+        &format!("< {} >", group.to_uppercase()),
+        Color {
+            r: 1.0,
+            g: 1.0,
+            b: 1.0,
+            a: 1.0,
+        },
+    );
+
+    for (index, word) in words.iter().enumerate() {
+        let direction = WordPicker::slot_direction(index, words.len()) * RADIUS;
+        let color = if selected == Some(index) {
+            Color {
+                r: 1.0,
+                g: 0.8,
+                b: 0.1,
+                a: 1.0,
+            }
+        } else {
+            Color {
+                r: 0.9,
+                g: 0.9,
+                b: 0.9,
+                a: 1.0,
+            }
+        };
+        label(
+            (direction.x, direction.y),
+            SLOT_HALF_SIZE,
+            &word.to_uppercase(),
+            color,
+        );
+    }
+}
+
+fn label(center: (f32, f32), half_size: (f32, f32), text: &str, color: Color) {
+    let layout = ContentBoxItemLayout {
+        anchors: 0.5.into(),
+        margin: Rect {
+            left: center.0 - half_size.0,
+            right: -(center.0 + half_size.0),
+            top: center.1 - half_size.1,
+            bottom: -(center.1 + half_size.1),
+        },
+        ..Default::default()
+    };
+
+    content_box(layout, || {
+        image_box(ImageBoxProps {
+            material: ImageBoxMaterial::Image(ImageBoxImage {
+                id: "ui/panel".to_owned(),
+                scaling: ImageBoxImageScaling::Frame(ImageBoxFrame {
+                    source: 0.5.into(),
+                    destination: 16.0.into(),
+                    frame_only: false,
+                    frame_keep_aspect_ratio: false,
+                }),
+                tint: Color {
+                    r: 0.2,
+                    g: 0.2,
+                    b: 0.2,
+                    a: 0.8,
+                },
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+
+        text_box(TextBoxProps {
+            text: text.to_owned(),
+            horizontal_align: TextBoxHorizontalAlign::Center,
+            vertical_align: TextBoxVerticalAlign::Middle,
+            font: TextBoxFont {
+                name: "roboto".to_owned(),
+                size: 24.0,
+            },
+            color,
+            ..Default::default()
+        });
+    });
+}
@@ -1,38 +1,24 @@
-use micro_games_kit::third_party::{
-    kira::{
-        manager::{AudioManager, AudioManagerSettings},
-        sound::static_sound::{StaticSoundData, StaticSoundHandle},
-    },
-    raui_core::{Managed, ManagedRef, ManagedRefMut},
+use micro_games_kit::third_party::kira::{
+    manager::{AudioManager, AudioManagerSettings},
+    sound::static_sound::{StaticSoundData, StaticSoundHandle},
 };
 use std::{collections::HashMap, io::Cursor};
 
-thread_local! {
-    static INSTANCE: Managed<Audio> = Default::default();
-}
-
+/// Default audio is silent - it has no manager, so sounds are registered but
+/// never played. Useful for headless simulations and tests.
+#[derive(Default)]
 pub struct Audio {
-    pub manager: AudioManager,
+    pub manager: Option<AudioManager>,
     sounds: HashMap<String, StaticSoundData>,
 }
 
-impl Default for Audio {
-    fn default() -> Self {
+impl Audio {
+    pub fn new() -> Self {
         Self {
-            manager: AudioManager::new(AudioManagerSettings::default()).unwrap(),
+            manager: AudioManager::new(AudioManagerSettings::default()).ok(),
             sounds: Default::default(),
         }
     }
-}
-
-impl Audio {
-    pub fn read() -> ManagedRef<Self> {
-        INSTANCE.with(|instance| instance.lazy().borrow().unwrap())
-    }
-
-    pub fn write() -> ManagedRefMut<Self> {
-        INSTANCE.with(|instance| instance.lazy().borrow_mut().unwrap())
-    }
 
     pub fn sound(&self, id: &str) -> Option<StaticSoundData> {
         self.sounds.get(id).cloned()
@@ -46,6 +32,9 @@ impl Audio {
     }
 
     pub fn play(&mut self, id: &str) -> Option<StaticSoundHandle> {
-        self.manager.play(self.sounds.get(id)?.clone()).ok()
+        self.manager
+            .as_mut()?
+            .play(self.sounds.get(id)?.clone())
+            .ok()
     }
 }
@@ -0,0 +1,502 @@
+use crate::game::{
+    components::{
+        affix::{Affix, Elite, Hasted, Reflective, Shielded, Teleporting, Unstable, Vampiric},
+        animation::Animation,
+        boss::{Boss, BossPhase},
+        collidable::Collidable,
+        damage::{Damage, DamageLayer},
+        effect::Effect,
+        enemy::Enemy,
+        enemy_ai::{EnemyAi, EnemyBehaviour},
+        flocking::Flocking,
+        health::Health,
+        immobility::Immobility,
+        knockback::Knockback,
+        on_death::{DeathBehaviour, OnDeath},
+        speed::Speed,
+        spell_loadout::SpellLoadout,
+        sprite_data::SpriteData,
+        velocity::Velocity,
+    },
+    utils::{
+        magic::spell_tag::SpellTagEffect,
+        space::{SpaceCollider, SpaceObject},
+        waves::ElementWeights,
+    },
+};
+use hecs::{Entity, World};
+use micro_games_kit::{
+    animation::{FrameAnimation, NamedAnimation},
+    third_party::{
+        rand::{seq::SliceRandom, thread_rng, Rng},
+        serde::Deserialize,
+        toml,
+        vek::{Transform, Vec2},
+    },
+};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(crate = "micro_games_kit::third_party::serde")]
+pub struct EnemyAnimationData {
+    pub id: String,
+    pub frames: usize,
+    pub fps: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(crate = "micro_games_kit::third_party::serde")]
+pub struct BossData {
+    pub title: String,
+    pub phases: Vec<BossPhase>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(crate = "micro_games_kit::third_party::serde")]
+pub struct EnemyKindData {
+    #[serde(default = "EnemyKindData::default_weight")]
+    pub weight: u32,
+    pub health: f32,
+    pub speed: [f32; 2],
+    pub speed_limit: f32,
+    pub acceleration: f32,
+    pub collider_radius: f32,
+    pub contact_damage: f32,
+    #[serde(default = "EnemyKindData::default_scale")]
+    pub scale: f32,
+    #[serde(default = "EnemyKindData::default_mass")]
+    pub mass: f32,
+    #[serde(default)]
+    pub elements: Option<ElementWeights>,
+    #[serde(default = "EnemyKindData::default_shoot_cooldown")]
+    pub shoot_cooldown: [f32; 2],
+    /// Words spoken in incantations, with their weights.
+    #[serde(default)]
+    pub words: BTreeMap<String, u32>,
+    #[serde(default = "EnemyKindData::default_word_count")]
+    pub word_count: [usize; 2],
+    #[serde(default = "EnemyKindData::default_wind_up")]
+    pub wind_up: f32,
+    pub animation: EnemyAnimationData,
+    #[serde(default)]
+    pub flocking: Flocking,
+    /// Name of behaviour graph, default one when missing.
+    #[serde(default)]
+    pub behaviour: Option<String>,
+    #[serde(default)]
+    pub boss: Option<BossData>,
+    #[serde(default)]
+    pub on_death: Vec<DeathBehaviour>,
+}
+
+impl EnemyKindData {
+    fn default_weight() -> u32 {
+        1
+    }
+
+    fn default_scale() -> f32 {
+        1.0
+    }
+
+    fn default_mass() -> f32 {
+        1.0
+    }
+
+    fn default_shoot_cooldown() -> [f32; 2] {
+        SpellLoadout::default().cooldown
+    }
+
+    fn default_word_count() -> [usize; 2] {
+        SpellLoadout::default().word_count
+    }
+
+    fn default_wind_up() -> f32 {
+        SpellLoadout::default().wind_up
+    }
+
+    /// Wave element weights biased by elements this kind prefers.
+    pub fn element_weights(
+        &self,
+        wave_weights: &[(SpellTagEffect, u32)],
+    ) -> Vec<(SpellTagEffect, u32)> {
+        let Some(elements) = self.elements.as_ref() else {
+            return wave_weights.to_vec();
+        };
+        let result = wave_weights
+            .iter()
+            .map(|(effect, weight)| (*effect, weight * elements.weight(*effect)))
+            .collect::<Vec<_>>();
+        // kind does not like any element of this wave - take what wave gives.
+        if result.iter().all(|(_, weight)| *weight == 0) {
+            wave_weights.to_vec()
+        } else {
+            result
+        }
+    }
+
+    pub fn spawn(
+        &self,
+        world: &mut World,
+        position: Vec2<f32>,
+        effect: Effect,
+        behaviour: EnemyBehaviour,
+    ) -> Entity {
+        let loadout = SpellLoadout {
+            words: self
+                .words
+                .iter()
+                .map(|(word, weight)| (word.to_owned(), *weight))
+                .collect(),
+            word_count: self.word_count,
+            cooldown: self.shoot_cooldown,
+            wind_up: self.wind_up,
+        };
+        let [speed_min, speed_max] = self.speed;
+
+        let entity = world.spawn((
+            Enemy {
+                acceleration: self.acceleration,
+                speed_limit: self.speed_limit,
+                shoot_cooldown: loadout.next_cooldown(),
+                ..Default::default()
+            },
+            EnemyAi::new(behaviour),
+            Animation {
+                animation: Some(NamedAnimation {
+                    animation: FrameAnimation::new(0..self.animation.frames)
+                        .fps(self.animation.fps)
+                        .looping()
+                        .playing(),
+                    id: self.animation.id.to_owned(),
+                }),
+            },
+            Transform::<f32, f32, f32> {
+                position: position.into(),
+                scale: self.scale.into(),
+                ..Default::default()
+            },
+            Collidable {
+                space_object: Some(SpaceObject {
+                    entity: None,
+                    position,
+                    collider: SpaceCollider::circle(self.collider_radius),
+                }),
+            },
+            SpriteData {
+                texture: format!("{}/0", self.animation.id).into(),
+                ..Default::default()
+            },
+            effect,
+            Health {
+                value: self.health,
+                limit: self.health,
+                layer: DamageLayer::Enemy,
+            },
+            Damage {
+                value: self.contact_damage,
+                layer: DamageLayer::Enemy,
+            },
+            Speed::new(speed_min..=speed_max.max(speed_min)),
+            Immobility { time_left: 0.0 },
+            self.flocking,
+            loadout,
+        ));
+        let _ = world.insert(entity, (Velocity::default(), Knockback::new(self.mass)));
+        if let Some(boss) = self.boss.as_ref() {
+            let _ = world.insert_one(entity, Boss::new(&boss.title, boss.phases.clone()));
+        }
+        if !self.on_death.is_empty() {
+            let _ = world.insert_one(
+                entity,
+                OnDeath {
+                    behaviours: self.on_death.clone(),
+                },
+            );
+        }
+        entity
+    }
+}
+
+/// How often spawned enemies become elites and how strong their affixes are.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(crate = "micro_games_kit::third_party::serde", default)]
+pub struct EliteData {
+    /// Chance at the start of the run.
+    pub chance: f32,
+    /// Chance added for every minute survived.
+    pub chance_per_minute: f32,
+    pub chance_limit: f32,
+    /// Range of number of affixes an elite rolls.
+    pub affix_count: [usize; 2],
+    /// Shield as fraction of health limit.
+    pub shield: f32,
+    pub haste: f32,
+    /// Fraction of contact damage healed back.
+    pub vampiric: f32,
+    pub teleport_interval: f32,
+    pub teleport_distance: f32,
+    pub unstable_interval: f32,
+}
+
+impl Default for EliteData {
+    fn default() -> Self {
+        Self {
+            chance: 0.05,
+            chance_per_minute: 0.05,
+            chance_limit: 0.5,
+            affix_count: [1, 2],
+            shield: 0.5,
+            haste: 1.5,
+            vampiric: 0.5,
+            teleport_interval: 5.0,
+            teleport_distance: 250.0,
+            unstable_interval: 3.0,
+        }
+    }
+}
+
+impl EliteData {
+    pub fn chance(&self, alive_time_seconds: f32) -> f32 {
+        (self.chance + self.chance_per_minute * alive_time_seconds / 60.0).min(self.chance_limit)
+    }
+
+    /// Affixes of newly spawned enemy, empty when it does not become elite.
+    pub fn roll(&self, alive_time_seconds: f32) -> Vec<Affix> {
+        let mut rng = thread_rng();
+        if rng.gen_range(0.0..1.0) >= self.chance(alive_time_seconds) {
+            return vec![];
+        }
+        let [min, max] = self.affix_count;
+        let count = rng.gen_range(min..=max.max(min));
+        let mut affixes = Affix::ALL
+            .choose_multiple(&mut rng, count)
+            .copied()
+            .collect::<Vec<_>>();
+        affixes.sort();
+        affixes
+    }
+
+    pub fn apply(&self, world: &mut World, entity: Entity, affixes: Vec<Affix>) {
+        if affixes.is_empty() {
+            return;
+        }
+        let health_limit = world
+            .get::<&Health>(entity)
+            .map(|health| health.limit)
+            .unwrap_or_default();
+        for affix in &affixes {
+            let _ = match affix {
+                Affix::Shielded => world.insert_one(
+                    entity,
+                    Shielded {
+                        value: health_limit * self.shield,
+                    },
+                ),
+                Affix::Hasted => world.insert_one(
+                    entity,
+                    Hasted {
+                        multiplier: self.haste,
+                    },
+                ),
+                Affix::Vampiric => world.insert_one(
+                    entity,
+                    Vampiric {
+                        fraction: self.vampiric,
+                    },
+                ),
+                Affix::Teleporting => world.insert_one(
+                    entity,
+                    Teleporting {
+                        interval: self.teleport_interval,
+                        distance: self.teleport_distance,
+                        time_left: self.teleport_interval,
+                    },
+                ),
+                Affix::Reflective => world.insert_one(entity, Reflective),
+                Affix::Unstable => world.insert_one(
+                    entity,
+                    Unstable {
+                        interval: self.unstable_interval,
+                        time_left: self.unstable_interval,
+                    },
+                ),
+            };
+        }
+        let _ = world.insert_one(entity, Elite { affixes });
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+#[serde(crate = "micro_games_kit::third_party::serde")]
+pub struct EnemyDatabase {
+    pub kinds: BTreeMap<String, EnemyKindData>,
+    #[serde(default)]
+    pub behaviours: BTreeMap<String, EnemyBehaviour>,
+    #[serde(default)]
+    pub elites: EliteData,
+}
+
+impl EnemyDatabase {
+    pub fn load() -> Self {
+        Self::load_from_str(include_str!("../../../assets/data/enemies.toml"))
+            .expect("Could not load enemy kinds!")
+    }
+
+    pub fn load_from_str(content: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(content)
+    }
+
+    pub fn kind(&self, name: &str) -> Option<&EnemyKindData> {
+        self.kinds.get(name)
+    }
+
+    pub fn behaviour(&self, kind: &EnemyKindData) -> EnemyBehaviour {
+        kind.behaviour
+            .as_ref()
+            .and_then(|name| self.behaviours.get(name))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Default spawn weights of all kinds.
+    pub fn weights(&self) -> Vec<(String, u32)> {
+        self.kinds
+            .iter()
+            .map(|(name, kind)| (name.to_owned(), kind.weight))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::utils::waves::WaveScript;
+
+    #[test]
+    fn test_enemy_database() {
+        let database = EnemyDatabase::load_from_str(
+            r#"
+            [kinds.blob]
+            health = 80.0
+            speed = [10.0, 20.0]
+            speed_limit = 30.0
+            acceleration = 5.0
+            collider_radius = 40.0
+            contact_damage = 3.0
+            elements = { water = 1 }
+            shoot_cooldown = [1.0, 2.0]
+            animation = { id = "slime", frames = 1, fps = 10.0 }
+            flocking = { cohesion = 0.5 }
+            words = { big = 2, slow = 1 }
+            "#,
+        )
+        .unwrap();
+        let blob = database.kind("blob").unwrap();
+        assert_eq!(blob.weight, 1);
+        assert_eq!(blob.scale, 1.0);
+        assert_eq!(blob.flocking.cohesion, 0.5);
+        assert_eq!(blob.flocking.separation, Flocking::default().separation);
+        assert_eq!(blob.word_count, SpellLoadout::default().word_count);
+
+        // preferred elements filter wave elements, unless nothing is left.
+        let wave = ElementWeights {
+            none: 1,
+            water: 2,
+            ..Default::default()
+        }
+        .to_weights();
+        assert_eq!(
+            blob.element_weights(&wave),
+            vec![
+                (SpellTagEffect::None, 0),
+                (SpellTagEffect::Fire, 0),
+                (SpellTagEffect::Water, 2),
+                (SpellTagEffect::Electric, 0),
+            ]
+        );
+        let wave = ElementWeights {
+            fire: 1,
+            ..Default::default()
+        }
+        .to_weights();
+        assert_eq!(blob.element_weights(&wave), wave);
+
+        let mut world = World::new();
+        let entity = blob.spawn(
+            &mut world,
+            Vec2::new(5.0, 0.0),
+            Effect::default(),
+            database.behaviour(blob),
+        );
+        let health = *world.get::<&Health>(entity).unwrap();
+        assert_eq!(health.value, 80.0);
+        assert_eq!(world.get::<&Damage>(entity).unwrap().value, 3.0);
+        let speed = world.get::<&Speed>(entity).unwrap().value;
+        assert!((10.0..=20.0).contains(&speed));
+        let loadout = world.get::<&SpellLoadout>(entity).unwrap();
+        assert!((1.0..=2.0).contains(&loadout.next_cooldown()));
+        assert_eq!(
+            loadout.words,
+            vec![("big".to_owned(), 2), ("slow".to_owned(), 1)]
+        );
+
+        // shipped kinds must parse, trucks outrun and are weaker than slimes.
+        let database = EnemyDatabase::load();
+        let slime = database.kind("slime").unwrap();
+        let truck = database.kind("truck").unwrap();
+        assert!(truck.speed_limit > slime.speed_limit);
+        assert!(truck.health < slime.health);
+        assert!(!slime.words.is_empty() && !truck.words.is_empty());
+        for kind in database.kinds.values() {
+            if let Some(name) = kind.behaviour.as_ref() {
+                assert!(database.behaviours.contains_key(name), "{}", name);
+            }
+        }
+        for wave in WaveScript::load().waves {
+            for kind in wave.enemies.keys() {
+                assert!(database.kind(kind).is_some(), "{}", kind);
+            }
+        }
+    }
+
+    #[test]
+    fn test_elite_affixes() {
+        let elites = EliteData {
+            chance: 0.0,
+            chance_per_minute: 0.5,
+            chance_limit: 1.0,
+            affix_count: [6, 6],
+            ..Default::default()
+        };
+        // elites get more common the longer player survives.
+        assert_eq!(elites.chance(0.0), 0.0);
+        assert_eq!(elites.chance(60.0), 0.5);
+        assert_eq!(elites.chance(600.0), 1.0);
+        assert!(elites.roll(0.0).is_empty());
+        let affixes = elites.roll(600.0);
+        assert_eq!(affixes, Affix::ALL.to_vec());
+
+        let mut world = World::new();
+        let entity = world.spawn((Health {
+            value: 100.0,
+            limit: 100.0,
+            layer: DamageLayer::Enemy,
+        },));
+        elites.apply(&mut world, entity, affixes);
+        assert_eq!(world.get::<&Shielded>(entity).unwrap().value, 50.0);
+        assert!(world.get::<&Hasted>(entity).is_ok());
+        assert!(world.get::<&Vampiric>(entity).is_ok());
+        assert!(world.get::<&Teleporting>(entity).is_ok());
+        assert!(world.get::<&Reflective>(entity).is_ok());
+        assert!(world.get::<&Unstable>(entity).is_ok());
+        assert_eq!(
+            world.get::<&Elite>(entity).unwrap().title(),
+            "Shielded, Hasted, Vampiric, Teleporting, Reflective, Elementally Unstable"
+        );
+
+        // shipped elites start rare.
+        let elites = EnemyDatabase::load().elites;
+        assert!(elites.chance(0.0) < elites.chance(300.0));
+        assert!(elites.chance(3600.0) <= elites.chance_limit);
+    }
+}
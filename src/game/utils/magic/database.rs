@@ -1,6 +1,8 @@
-use micro_games_kit::third_party::rand::{thread_rng, Rng};
+use micro_games_kit::third_party::rand::{
+    distributions::WeightedIndex, prelude::Distribution, seq::SliceRandom, thread_rng, Rng,
+};
 
-use super::spell_tag::SpellTag;
+use super::spell_tag::{SpellTag, SpellTagEffect};
 use crate::{game::components::spell::Spell, hash_set};
 use std::collections::{HashMap, HashSet};
 
@@ -19,47 +21,69 @@ impl WordToSpellTagDatabase {
     }
 
     pub fn parse(&self, text: &str) -> Option<Spell> {
+        self.parse_with_effect(text, None)
+    }
+
+    /// Parses text into spell of given effect when it names none, instead of
+    /// falling back to basic spell, so spells of casters without element are
+    /// still shaped by their words.
+    pub fn parse_with_effect(
+        &self,
+        text: &str,
+        default_effect: Option<SpellTagEffect>,
+    ) -> Option<Spell> {
         // convert words to tags (or generate random ones in their place).
         let tags = text
             .split_whitespace()
-            .map(|word| {
+            .flat_map(|word| {
                 self.records
                     .get(word)
                     .cloned()
                     .unwrap_or_else(|| hash_set![SpellTag::random()])
             })
-            .flatten()
             .collect::<HashSet<_>>();
         // construct structured spell tags with required categories or use defaults.
-        if let Some(effect) = tags.iter().find_map(|tag| tag.as_effect()) {
+        // summons do not need an element, they fight with their bodies too.
+        let summon = tags.iter().find_map(|tag| tag.as_summon());
+        let trigger = tags.iter().find_map(|tag| tag.as_trigger());
+        let effect = tags
+            .iter()
+            .find_map(|tag| tag.as_effect())
+            .or(default_effect);
+        if effect.is_some() || summon.is_some() {
+            let effect = effect.unwrap_or(SpellTagEffect::None);
             let size = tags
                 .iter()
                 .find_map(|tag| tag.as_size())
-                .unwrap_or_else(|| Default::default());
+                .unwrap_or_default();
             let speed = tags
                 .iter()
                 .find_map(|tag| tag.as_speed())
-                .unwrap_or_else(|| Default::default());
+                .unwrap_or_default();
             let shape = tags
                 .iter()
                 .find_map(|tag| tag.as_shape())
-                .unwrap_or_else(|| Default::default());
+                .unwrap_or_default();
             let direction = tags
                 .iter()
                 .find_map(|tag| tag.as_direction())
-                .unwrap_or_else(|| Default::default());
+                .unwrap_or_default();
             let trajectory = tags
                 .iter()
                 .find_map(|tag| tag.as_trajectory())
-                .unwrap_or_else(|| Default::default());
+                .unwrap_or_default();
             let duration = tags
                 .iter()
                 .find_map(|tag| tag.as_duration())
-                .unwrap_or_else(|| Default::default());
+                .unwrap_or_default();
             let damage = tags
                 .iter()
                 .find_map(|tag| tag.as_damage())
-                .unwrap_or_else(|| Default::default());
+                .unwrap_or_default();
+            let target = tags
+                .iter()
+                .find_map(|tag| tag.as_target())
+                .unwrap_or_default();
             Some(Spell {
                 size,
                 speed,
@@ -69,6 +93,9 @@ impl WordToSpellTagDatabase {
                 trajectory,
                 duration,
                 damage,
+                target,
+                summon,
+                trigger,
             })
         } else {
             Some(Spell::basic())
@@ -80,6 +107,41 @@ impl WordToSpellTagDatabase {
         let index = thread_rng().gen_range(0..count);
         self.records.keys().nth(index).cloned().unwrap_or_default()
     }
+
+    /// Random word meaning given tag, words meaning nothing else are preferred.
+    pub fn random_word_for(&self, tag: SpellTag) -> Option<String> {
+        let mut words = self
+            .records
+            .iter()
+            .filter(|(_, tags)| tags.contains(&tag))
+            .collect::<Vec<_>>();
+        if words.iter().any(|(_, tags)| tags.len() == 1) {
+            words.retain(|(_, tags)| tags.len() == 1);
+        }
+        words
+            .choose(&mut thread_rng())
+            .map(|(word, _)| word.to_string())
+    }
+
+    /// Composes incantation of `count` words picked by weight from known ones,
+    /// led by a word of given effect so it can be read who casts what.
+    pub fn compose(&self, effect: SpellTagEffect, words: &[(String, u32)], count: usize) -> String {
+        let mut result = Vec::with_capacity(count + 1);
+        if effect != SpellTagEffect::None {
+            result.extend(self.random_word_for(SpellTag::Effect(effect)));
+        }
+        let words = words
+            .iter()
+            .filter(|(word, _)| self.records.contains_key(word))
+            .collect::<Vec<_>>();
+        if let Ok(distribution) = WeightedIndex::new(words.iter().map(|(_, weight)| *weight)) {
+            let mut rng = thread_rng();
+            for _ in 0..count {
+                result.push(words[distribution.sample(&mut rng)].0.to_owned());
+            }
+        }
+        result.join(" ")
+    }
 }
 
 #[cfg(test)]
@@ -87,7 +149,10 @@ mod tests {
     use super::WordToSpellTagDatabase;
     use crate::game::{
         components::spell::Spell,
-        utils::magic::spell_tag::{SpellTag, SpellTagEffect, SpellTagShape, SpellTagSize},
+        utils::magic::spell_tag::{
+            SpellTag, SpellTagEffect, SpellTagShape, SpellTagSize, SpellTagSpeed, SpellTagSummon,
+            SpellTagTrigger,
+        },
     };
 
     #[test]
@@ -112,6 +177,9 @@ mod tests {
                 trajectory: Default::default(),
                 duration: Default::default(),
                 damage: Default::default(),
+                target: Default::default(),
+                summon: None,
+                trigger: None,
             }
         );
 
@@ -127,9 +195,22 @@ mod tests {
                 trajectory: Default::default(),
                 duration: Default::default(),
                 damage: Default::default(),
+                target: Default::default(),
+                summon: None,
+                trigger: None,
             }
         );
 
+        let database = database.with("golem", SpellTag::Summon(SpellTagSummon::Golem));
+        let spell = database.parse("golem").unwrap();
+        assert_eq!(spell.summon, Some(SpellTagSummon::Golem));
+        assert_eq!(spell.effect, SpellTagEffect::None);
+
+        let database = database.with("later", SpellTag::Trigger(SpellTagTrigger::Delay));
+        let spell = database.parse("fire later").unwrap();
+        assert_eq!(spell.trigger, Some(SpellTagTrigger::Delay));
+        assert_eq!(spell.effect, SpellTagEffect::Fire);
+
         let spell = database.parse("fire").unwrap();
         assert_eq!(
             spell,
@@ -142,7 +223,51 @@ mod tests {
                 trajectory: Default::default(),
                 duration: Default::default(),
                 damage: Default::default(),
+                target: Default::default(),
+                summon: None,
+                trigger: None,
             }
         );
     }
+
+    #[test]
+    fn test_compose_incantation() {
+        let database = WordToSpellTagDatabase::default()
+            .with("fire", SpellTag::Effect(SpellTagEffect::Fire))
+            .with("meteor", SpellTag::Effect(SpellTagEffect::Fire))
+            .with("meteor", SpellTag::Size(SpellTagSize::Large))
+            .with("wet", SpellTag::Effect(SpellTagEffect::Water))
+            .with("ball", SpellTag::Shape(SpellTagShape::Point))
+            .with("slow", SpellTag::Speed(SpellTagSpeed::Slow));
+
+        // words meaning only the element win over ones with side effects.
+        assert_eq!(
+            database.random_word_for(SpellTag::Effect(SpellTagEffect::Fire)),
+            Some("fire".to_owned())
+        );
+        assert_eq!(
+            database.random_word_for(SpellTag::Size(SpellTagSize::Large)),
+            Some("meteor".to_owned())
+        );
+
+        let words = vec![
+            ("slow".to_owned(), 1),
+            ("ball".to_owned(), 0),
+            ("unknown".to_owned(), 100),
+        ];
+        let text = database.compose(SpellTagEffect::Fire, &words, 2);
+        assert_eq!(text, "fire slow slow");
+        let spell = database.parse(&text).unwrap();
+        assert_eq!(spell.effect, SpellTagEffect::Fire);
+        assert_eq!(spell.speed, SpellTagSpeed::Slow);
+
+        assert_eq!(database.compose(SpellTagEffect::None, &words, 1), "slow");
+        // words without element still shape spell of caster without one.
+        let spell = database
+            .parse_with_effect("slow", Some(SpellTagEffect::None))
+            .unwrap();
+        assert_eq!(spell.effect, SpellTagEffect::None);
+        assert_eq!(spell.speed, SpellTagSpeed::Slow);
+        assert_eq!(database.compose(SpellTagEffect::Water, &[], 3), "wet");
+    }
 }
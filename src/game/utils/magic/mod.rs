@@ -1,2 +1,3 @@
 pub mod database;
 pub mod spell_tag;
+pub mod spellbook;
@@ -1,5 +1,6 @@
 use micro_games_kit::third_party::{
     rand::{thread_rng, Rng},
+    serde::Deserialize,
     vek::Vec2,
 };
 
@@ -13,9 +14,76 @@ pub enum SpellTag {
     Trajectory(SpellTagTrajectory),
     Duration(SpellTagDuration),
     Damage(SpellTagDamage),
+    Target(SpellTagTarget),
+    Summon(SpellTagSummon),
+    Trigger(SpellTagTrigger),
+}
+
+/// Kind of spell property tag is about, without its value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SpellTagCategory {
+    Effect,
+    Shape,
+    Size,
+    Speed,
+    Direction,
+    Trajectory,
+    Duration,
+    Damage,
+    Target,
+    Summon,
+    Trigger,
+}
+
+impl SpellTagCategory {
+    pub const ALL: [Self; 11] = [
+        Self::Effect,
+        Self::Shape,
+        Self::Size,
+        Self::Speed,
+        Self::Direction,
+        Self::Trajectory,
+        Self::Duration,
+        Self::Damage,
+        Self::Target,
+        Self::Summon,
+        Self::Trigger,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Effect => "Effect",
+            Self::Shape => "Shape",
+            Self::Size => "Size",
+            Self::Speed => "Speed",
+            Self::Direction => "Direction",
+            Self::Trajectory => "Trajectory",
+            Self::Duration => "Duration",
+            Self::Damage => "Damage",
+            Self::Target => "Target",
+            Self::Summon => "Summon",
+            Self::Trigger => "Trigger",
+        }
+    }
 }
 
 impl SpellTag {
+    pub fn category(&self) -> SpellTagCategory {
+        match self {
+            Self::Size(_) => SpellTagCategory::Size,
+            Self::Speed(_) => SpellTagCategory::Speed,
+            Self::Effect(_) => SpellTagCategory::Effect,
+            Self::Shape(_) => SpellTagCategory::Shape,
+            Self::Direction(_) => SpellTagCategory::Direction,
+            Self::Trajectory(_) => SpellTagCategory::Trajectory,
+            Self::Duration(_) => SpellTagCategory::Duration,
+            Self::Damage(_) => SpellTagCategory::Damage,
+            Self::Target(_) => SpellTagCategory::Target,
+            Self::Summon(_) => SpellTagCategory::Summon,
+            Self::Trigger(_) => SpellTagCategory::Trigger,
+        }
+    }
+
     pub fn random() -> Self {
         match thread_rng().gen_range(0..7) {
             0 => Self::Size(SpellTagSize::random()),
@@ -85,9 +153,34 @@ impl SpellTag {
             _ => None,
         }
     }
+
+    pub fn as_target(&self) -> Option<SpellTagTarget> {
+        match self {
+            Self::Target(result) => Some(*result),
+            _ => None,
+        }
+    }
+
+    pub fn as_summon(&self) -> Option<SpellTagSummon> {
+        match self {
+            Self::Summon(result) => Some(*result),
+            _ => None,
+        }
+    }
+
+    pub fn as_trigger(&self) -> Option<SpellTagTrigger> {
+        match self {
+            Self::Trigger(result) => Some(*result),
+            _ => None,
+        }
+    }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize)]
+#[serde(
+    crate = "micro_games_kit::third_party::serde",
+    rename_all = "snake_case"
+)]
 pub enum SpellTagEffect {
     None,
     Fire,
@@ -113,9 +206,24 @@ impl SpellTagEffect {
             SpellTagEffect::Water => "particle/water".into(),
         }
     }
+
+    /// Elements beat each other in a cycle when spells clash: water puts out
+    /// fire, fire burns through electricity and electricity splits water.
+    pub fn counters(&self, other: Self) -> bool {
+        matches!(
+            (self, other),
+            (Self::Water, Self::Fire)
+                | (Self::Fire, Self::Electric)
+                | (Self::Electric, Self::Water)
+        )
+    }
 }
 
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize)]
+#[serde(
+    crate = "micro_games_kit::third_party::serde",
+    rename_all = "snake_case"
+)]
 pub enum SpellTagTrajectory {
     #[default]
     Straight,
@@ -134,7 +242,11 @@ impl SpellTagTrajectory {
     }
 }
 
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize)]
+#[serde(
+    crate = "micro_games_kit::third_party::serde",
+    rename_all = "snake_case"
+)]
 pub enum SpellTagSize {
     Small,
     #[default]
@@ -154,9 +266,9 @@ impl SpellTagSize {
 
     pub fn scale(&self) -> Vec2<f32> {
         match self {
-            SpellTagSize::Large => Vec2::new(4.0, 4.0).into(),
-            SpellTagSize::Medium => Vec2::new(2.0, 2.0).into(),
-            SpellTagSize::Small => Vec2::new(1.0, 1.0).into(),
+            SpellTagSize::Large => Vec2::new(4.0, 4.0),
+            SpellTagSize::Medium => Vec2::new(2.0, 2.0),
+            SpellTagSize::Small => Vec2::new(1.0, 1.0),
         }
     }
 
@@ -168,6 +280,15 @@ impl SpellTagSize {
         }
     }
 
+    /// Impulse given to things hit, small spells do not push.
+    pub fn push_impulse(&self) -> f32 {
+        match self {
+            SpellTagSize::Large => 200.0,
+            SpellTagSize::Medium => 60.0,
+            SpellTagSize::Small => 0.0,
+        }
+    }
+
     pub fn scale_offset(&self) -> f32 {
         match self {
             SpellTagSize::Large => 2.0,
@@ -177,7 +298,11 @@ impl SpellTagSize {
     }
 }
 
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize)]
+#[serde(
+    crate = "micro_games_kit::third_party::serde",
+    rename_all = "snake_case"
+)]
 pub enum SpellTagSpeed {
     Slow,
     #[default]
@@ -196,7 +321,11 @@ impl SpellTagSpeed {
     }
 }
 
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize)]
+#[serde(
+    crate = "micro_games_kit::third_party::serde",
+    rename_all = "snake_case"
+)]
 pub enum SpellTagDuration {
     Instant,
     Quick,
@@ -225,7 +354,11 @@ impl SpellTagDuration {
     }
 }
 
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize)]
+#[serde(
+    crate = "micro_games_kit::third_party::serde",
+    rename_all = "snake_case"
+)]
 pub enum SpellTagShape {
     #[default]
     Point,
@@ -244,7 +377,11 @@ impl SpellTagShape {
     }
 }
 
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize)]
+#[serde(
+    crate = "micro_games_kit::third_party::serde",
+    rename_all = "snake_case"
+)]
 pub enum SpellTagDirection {
     #[default]
     Forward,
@@ -271,7 +408,11 @@ impl SpellTagDirection {
     }
 }
 
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize)]
+#[serde(
+    crate = "micro_games_kit::third_party::serde",
+    rename_all = "snake_case"
+)]
 pub enum SpellTagDamage {
     #[default]
     Low,
@@ -297,3 +438,108 @@ impl SpellTagDamage {
         }
     }
 }
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize)]
+#[serde(
+    crate = "micro_games_kit::third_party::serde",
+    rename_all = "snake_case"
+)]
+pub enum SpellTagTarget {
+    /// Spell starts next to its caster.
+    #[default]
+    Caster,
+    /// Spell starts at the place caster points at, within cast range.
+    Cursor,
+    /// Spell buffs its caster instead of hurting anything.
+    Oneself,
+}
+
+/// Ally summoned instead of casting projectiles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize)]
+#[serde(
+    crate = "micro_games_kit::third_party::serde",
+    rename_all = "snake_case"
+)]
+pub enum SpellTagSummon {
+    /// Slow and sturdy, blocks enemies with its body.
+    Golem,
+    /// Quick and frail, casts often.
+    Familiar,
+    /// Balanced, casts from a distance.
+    Spirit,
+    /// Cheap fodder.
+    Minion,
+}
+
+impl SpellTagSummon {
+    pub fn health(&self) -> f32 {
+        match self {
+            Self::Golem => 150.0,
+            Self::Familiar => 30.0,
+            Self::Spirit => 60.0,
+            Self::Minion => 40.0,
+        }
+    }
+
+    pub fn speed(&self) -> f32 {
+        match self {
+            Self::Golem => 80.0,
+            Self::Familiar => 260.0,
+            Self::Spirit => 180.0,
+            Self::Minion => 160.0,
+        }
+    }
+
+    pub fn radius(&self) -> f32 {
+        match self {
+            Self::Golem => 45.0,
+            Self::Familiar => 18.0,
+            Self::Spirit => 25.0,
+            Self::Minion => 25.0,
+        }
+    }
+
+    pub fn mass(&self) -> f32 {
+        match self {
+            Self::Golem => 8.0,
+            Self::Familiar => 0.5,
+            Self::Spirit => 1.0,
+            Self::Minion => 1.0,
+        }
+    }
+
+    /// Distance kept from enemies, golems walk right into them.
+    pub fn reach(&self) -> f32 {
+        match self {
+            Self::Golem => 0.0,
+            Self::Familiar => 300.0,
+            Self::Spirit => 200.0,
+            Self::Minion => 150.0,
+        }
+    }
+
+    /// Seconds between spells cast.
+    pub fn cooldown(&self) -> f32 {
+        match self {
+            Self::Golem => 3.0,
+            Self::Familiar => 0.8,
+            Self::Spirit => 1.5,
+            Self::Minion => 2.0,
+        }
+    }
+}
+
+/// Condition holding spell back until it is met.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize)]
+#[serde(
+    crate = "micro_games_kit::third_party::serde",
+    rename_all = "snake_case"
+)]
+pub enum SpellTagTrigger {
+    /// Fires after a while.
+    Delay,
+    /// Fires back at whoever hits its caster next.
+    Hit,
+    /// Lies on the ground until an enemy steps in.
+    Trap,
+}
@@ -0,0 +1,122 @@
+use super::{
+    database::WordToSpellTagDatabase,
+    spell_tag::{SpellTag, SpellTagCategory, SpellTagEffect},
+};
+use std::collections::BTreeSet;
+
+/// Words player has come across, so they can be picked without typing.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Spellbook {
+    words: BTreeSet<String>,
+}
+
+impl Spellbook {
+    /// Starts with one word for each element so there is always something to cast.
+    pub fn starter(database: &WordToSpellTagDatabase) -> Self {
+        let mut result = Self::default();
+        for effect in [
+            SpellTagEffect::Fire,
+            SpellTagEffect::Water,
+            SpellTagEffect::Electric,
+        ] {
+            result
+                .words
+                .extend(database.random_word_for(SpellTag::Effect(effect)));
+        }
+        result
+    }
+
+    /// Remembers words of text that mean something, returns newly discovered ones.
+    pub fn discover(&mut self, database: &WordToSpellTagDatabase, text: &str) -> Vec<String> {
+        text.split_whitespace()
+            .map(|word| word.to_lowercase())
+            .filter(|word| database.records.contains_key(word))
+            .filter(|word| self.words.insert(word.to_owned()))
+            .collect()
+    }
+
+    pub fn contains(&self, word: &str) -> bool {
+        self.words.contains(word)
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// Discovered words grouped by categories of their meanings, empty groups
+    /// are skipped. Word with many meanings shows up in each of their groups.
+    pub fn groups(
+        &self,
+        database: &WordToSpellTagDatabase,
+    ) -> Vec<(SpellTagCategory, Vec<String>)> {
+        SpellTagCategory::ALL
+            .into_iter()
+            .map(|category| {
+                let words = self
+                    .words
+                    .iter()
+                    .filter(|word| {
+                        database
+                            .records
+                            .get(*word)
+                            .map(|tags| tags.iter().any(|tag| tag.category() == category))
+                            .unwrap_or_default()
+                    })
+                    .cloned()
+                    .collect::<Vec<_>>();
+                (category, words)
+            })
+            .filter(|(_, words)| !words.is_empty())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::utils::magic::spell_tag::{SpellTagShape, SpellTagSize};
+
+    #[test]
+    fn test_spellbook() {
+        let database = WordToSpellTagDatabase::default()
+            .with("fire", SpellTag::Effect(SpellTagEffect::Fire))
+            .with("wet", SpellTag::Effect(SpellTagEffect::Water))
+            .with("zap", SpellTag::Effect(SpellTagEffect::Electric))
+            .with("ball", SpellTag::Shape(SpellTagShape::Point))
+            .with("meteor", SpellTag::Effect(SpellTagEffect::Fire))
+            .with("meteor", SpellTag::Size(SpellTagSize::Large));
+
+        let mut spellbook = Spellbook::starter(&database);
+        assert_eq!(spellbook.len(), 3);
+        assert!(spellbook.contains("fire") && !spellbook.contains("meteor"));
+
+        // only meaningful words are discovered, each of them once.
+        assert_eq!(
+            spellbook.discover(&database, "METEOR ball gibberish fire"),
+            vec!["meteor".to_owned(), "ball".to_owned()]
+        );
+        assert!(spellbook.discover(&database, "meteor ball").is_empty());
+
+        assert_eq!(
+            spellbook.groups(&database),
+            vec![
+                (
+                    SpellTagCategory::Effect,
+                    vec![
+                        "fire".to_owned(),
+                        "meteor".to_owned(),
+                        "wet".to_owned(),
+                        "zap".to_owned()
+                    ]
+                ),
+                (SpellTagCategory::Shape, vec!["ball".to_owned()]),
+                (SpellTagCategory::Size, vec!["meteor".to_owned()]),
+            ]
+        );
+        assert!(Spellbook::default().groups(&database).is_empty());
+    }
+}
@@ -0,0 +1,210 @@
+use crate::game::{
+    components::{
+        collidable::Collidable,
+        obstacle::{Obstacle, ObstacleProjectileResponse},
+        sprite_data::SpriteData,
+    },
+    utils::{
+        navigation::NavigationData,
+        space::{SpaceCollider, SpaceObject},
+    },
+};
+use hecs::World;
+use micro_games_kit::third_party::{
+    serde::Deserialize,
+    toml,
+    vek::{Quaternion, Transform, Vec2},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(
+    crate = "micro_games_kit::third_party::serde",
+    tag = "shape",
+    rename_all = "snake_case"
+)]
+pub enum ColliderData {
+    Circle { radius: f32 },
+    Capsule { radius: f32, half_length: f32 },
+    Box { half_extents: [f32; 2] },
+}
+
+impl ColliderData {
+    pub fn to_space_collider(&self, rotation: f32) -> SpaceCollider {
+        match *self {
+            Self::Circle { radius } => SpaceCollider::circle(radius),
+            Self::Capsule {
+                radius,
+                half_length,
+            } => SpaceCollider::Capsule {
+                radius,
+                half_length,
+                rotation,
+            },
+            Self::Box { half_extents } => {
+                SpaceCollider::oriented_box(Vec2::from(half_extents), rotation)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(crate = "micro_games_kit::third_party::serde")]
+pub struct ObstacleData {
+    pub kind: String,
+    pub position: [f32; 2],
+    // degrees.
+    #[serde(default)]
+    pub rotation: f32,
+    #[serde(default = "ObstacleData::default_scale")]
+    pub scale: f32,
+    #[serde(default)]
+    pub projectile: ObstacleProjectileResponse,
+    pub collider: ColliderData,
+}
+
+impl ObstacleData {
+    fn default_scale() -> f32 {
+        1.0
+    }
+}
+
+/// Area enemies come from, used when player is in its reach.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(crate = "micro_games_kit::third_party::serde")]
+pub struct SpawnZoneData {
+    pub position: [f32; 2],
+    pub radius: f32,
+    #[serde(default = "SpawnZoneData::default_weight")]
+    pub weight: u32,
+}
+
+impl SpawnZoneData {
+    fn default_weight() -> u32 {
+        1
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+#[serde(crate = "micro_games_kit::third_party::serde")]
+pub struct MapData {
+    #[serde(default)]
+    pub obstacles: Vec<ObstacleData>,
+    #[serde(default)]
+    pub navigation: NavigationData,
+    #[serde(default)]
+    pub spawn_zones: Vec<SpawnZoneData>,
+}
+
+impl MapData {
+    pub fn load() -> Self {
+        Self::load_from_str(include_str!("../../../assets/maps/map.toml"))
+            .expect("Could not load map data!")
+    }
+
+    pub fn load_from_str(content: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(content)
+    }
+
+    pub fn spawn_obstacles(&self, world: &mut World) {
+        for obstacle in &self.obstacles {
+            let position = Vec2::from(obstacle.position);
+            let rotation = obstacle.rotation.to_radians();
+
+            world.spawn((
+                Obstacle {
+                    projectile_response: obstacle.projectile,
+                },
+                Transform::<f32, f32, f32> {
+                    position: position.into(),
+                    orientation: Quaternion::rotation_z(rotation),
+                    scale: obstacle.scale.into(),
+                },
+                Collidable {
+                    space_object: Some(SpaceObject {
+                        entity: None,
+                        position,
+                        collider: obstacle.collider.to_space_collider(rotation),
+                    }),
+                },
+                SpriteData {
+                    texture: format!("obstacle/{}", obstacle.kind).into(),
+                    ..Default::default()
+                },
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_data() {
+        let map = MapData::load_from_str(
+            r#"
+            [[obstacles]]
+            kind = "rock"
+            position = [10.0, 20.0]
+            projectile = "bounce"
+            collider = { shape = "circle", radius = 50.0 }
+
+            [[obstacles]]
+            kind = "ruin"
+            position = [0.0, 0.0]
+            rotation = 90.0
+            scale = 2.0
+            collider = { shape = "box", half_extents = [100.0, 20.0] }
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            map.obstacles,
+            vec![
+                ObstacleData {
+                    kind: "rock".to_owned(),
+                    position: [10.0, 20.0],
+                    rotation: 0.0,
+                    scale: 1.0,
+                    projectile: ObstacleProjectileResponse::Bounce,
+                    collider: ColliderData::Circle { radius: 50.0 },
+                },
+                ObstacleData {
+                    kind: "ruin".to_owned(),
+                    position: [0.0, 0.0],
+                    rotation: 90.0,
+                    scale: 2.0,
+                    projectile: ObstacleProjectileResponse::Stop,
+                    collider: ColliderData::Box {
+                        half_extents: [100.0, 20.0],
+                    },
+                },
+            ]
+        );
+
+        // shipped map must parse and keep player spawn point free.
+        let map = MapData::load();
+        assert!(!map.obstacles.is_empty());
+        let spawn = SpaceObject {
+            collider: SpaceCollider::circle(100.0),
+            ..Default::default()
+        };
+        for obstacle in &map.obstacles {
+            let object = SpaceObject {
+                entity: None,
+                position: obstacle.position.into(),
+                collider: obstacle
+                    .collider
+                    .to_space_collider(obstacle.rotation.to_radians()),
+            };
+            assert!(!spawn.does_collide_narrow(&object), "{:?}", obstacle);
+        }
+        // spawn zones have to be inside the arena.
+        assert!(!map.spawn_zones.is_empty());
+        for zone in &map.spawn_zones {
+            let [x, y] = zone.position;
+            assert!(x.abs().max(y.abs()) + zone.radius <= 3500.0, "{:?}", zone);
+        }
+    }
+}
@@ -1,7 +1,13 @@
 pub mod audio;
+pub mod enemies;
 pub mod macros;
 pub mod magic;
+pub mod map;
+pub mod navigation;
 pub mod space;
+#[cfg(test)]
+pub mod test_utils;
+pub mod waves;
 
 use micro_games_kit::{context::GameContext, third_party::vek::Vec2};
 
@@ -13,6 +19,16 @@ pub fn world_to_screen(position: Vec2<f32>, context: &GameContext) -> Vec2<f32>
         .mul_point(position)
 }
 
+/// Inverse of `world_to_screen`, takes position in normalized device coordinates.
+pub fn screen_to_world(position: Vec2<f32>, context: &GameContext) -> Vec2<f32> {
+    context
+        .graphics
+        .main_camera
+        .world_matrix()
+        .inverted()
+        .mul_point(position)
+}
+
 pub fn world_to_screen_anchor(position: Vec2<f32>, context: &GameContext) -> Vec2<f32> {
     let position = world_to_screen(position, context);
     Vec2 {
@@ -0,0 +1,293 @@
+use crate::game::{
+    components::{obstacle::Obstacle, wall::Wall},
+    utils::space::{SpaceCollider, SpaceObject},
+};
+use hecs::{Entity, World};
+use micro_games_kit::third_party::{serde::Deserialize, vek::Vec2};
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+const STRAIGHT_COST: u32 = 10;
+const DIAGONAL_COST: u32 = 14;
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(crate = "micro_games_kit::third_party::serde", default)]
+pub struct NavigationData {
+    /// Half of the size of square area covered by grid, centered at world origin.
+    pub half_extent: f32,
+    pub cell_size: f32,
+    /// Wall spells living at least this many seconds are walked around.
+    pub wall_lifetime: f32,
+}
+
+impl Default for NavigationData {
+    fn default() -> Self {
+        Self {
+            half_extent: 4000.0,
+            cell_size: 80.0,
+            wall_lifetime: 1.0,
+        }
+    }
+}
+
+/// Grid of cells blocked by obstacles with flow field leading to target cell,
+/// so any number of enemies can share one path search.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NavigationGrid {
+    origin: Vec2<f32>,
+    cell_size: f32,
+    size: usize,
+    blocked: Vec<bool>,
+    // cost of the way to target from each cell, `u32::MAX` when unreachable.
+    costs: Vec<u32>,
+    flow: Vec<Vec2<f32>>,
+    target: Option<usize>,
+}
+
+impl Default for NavigationGrid {
+    fn default() -> Self {
+        Self::new(Default::default())
+    }
+}
+
+impl NavigationGrid {
+    pub fn new(data: NavigationData) -> Self {
+        let cell_size = data.cell_size.max(1.0);
+        let size = ((data.half_extent * 2.0 / cell_size).ceil() as usize).max(1);
+        Self {
+            origin: Vec2::broadcast(-data.half_extent),
+            cell_size,
+            size,
+            blocked: vec![false; size * size],
+            costs: vec![u32::MAX; size * size],
+            flow: vec![Vec2::zero(); size * size],
+            target: None,
+        }
+    }
+
+    /// Tells if entity blocks movement: static obstacle or long-lived wall spell.
+    pub fn is_blocker(world: &World, entity: Entity, wall_lifetime: f32) -> bool {
+        if let Ok(wall) = world.get::<&Wall>(entity) {
+            return wall.ttl >= wall_lifetime;
+        }
+        world.satisfies::<&Obstacle>(entity).unwrap_or_default()
+    }
+
+    pub fn cell(&self, position: Vec2<f32>) -> Option<usize> {
+        let local = (position - self.origin) / self.cell_size;
+        if local.x < 0.0 || local.y < 0.0 {
+            return None;
+        }
+        let (x, y) = (local.x as usize, local.y as usize);
+        (x < self.size && y < self.size).then_some(y * self.size + x)
+    }
+
+    pub fn cell_center(&self, index: usize) -> Vec2<f32> {
+        let x = (index % self.size) as f32 + 0.5;
+        let y = (index / self.size) as f32 + 0.5;
+        self.origin + Vec2::new(x, y) * self.cell_size
+    }
+
+    pub fn target(&self) -> Option<usize> {
+        self.target
+    }
+
+    pub fn is_blocked(&self, position: Vec2<f32>) -> bool {
+        self.cell(position)
+            .map(|index| self.blocked[index])
+            .unwrap_or_default()
+    }
+
+    /// Marks cells touched by given objects as blocked, everything else as
+    /// free. Returns true when any cell has changed.
+    pub fn set_blocked(&mut self, objects: impl IntoIterator<Item = SpaceObject>) -> bool {
+        let mut blocked = vec![false; self.blocked.len()];
+        let mut probe = SpaceObject {
+            entity: None,
+            position: Vec2::zero(),
+            collider: SpaceCollider::circle(self.cell_size * 0.5),
+        };
+
+        for object in objects {
+            let extents = object.collider.extents() + self.cell_size;
+            let from = ((object.position - extents - self.origin) / self.cell_size)
+                .map(|value| value.max(0.0) as usize);
+            let to = ((object.position + extents - self.origin) / self.cell_size)
+                .map(|value| (value.max(0.0) as usize).min(self.size - 1));
+            for y in from.y..=to.y {
+                for x in from.x..=to.x {
+                    let index = y * self.size + x;
+                    if blocked[index] {
+                        continue;
+                    }
+                    probe.position = self.cell_center(index);
+                    blocked[index] = probe.does_collide_narrow(&object);
+                }
+            }
+        }
+
+        let changed = blocked != self.blocked;
+        self.blocked = blocked;
+        changed
+    }
+
+    /// Recomputes flow field leading to cell at given position.
+    pub fn compute_flow(&mut self, target: Vec2<f32>) {
+        self.costs.fill(u32::MAX);
+        self.flow.fill(Vec2::zero());
+        self.target = self.cell(target);
+        let Some(target) = self.target else {
+            return;
+        };
+
+        // target cell is always reachable, player may stand close to a wall.
+        let mut open = BinaryHeap::new();
+        self.costs[target] = 0;
+        open.push(Reverse((0, target)));
+        while let Some(Reverse((cost, index))) = open.pop() {
+            if cost > self.costs[index] {
+                continue;
+            }
+            let neighbours = self.neighbours(index).collect::<Vec<_>>();
+            for (neighbour, step) in neighbours {
+                let cost = cost + step;
+                if cost < self.costs[neighbour] {
+                    self.costs[neighbour] = cost;
+                    open.push(Reverse((cost, neighbour)));
+                }
+            }
+        }
+
+        for index in 0..self.flow.len() {
+            if index == target || self.costs[index] == u32::MAX {
+                continue;
+            }
+            if let Some((next, _)) = self
+                .neighbours(index)
+                .min_by_key(|(neighbour, _)| self.costs[*neighbour])
+            {
+                self.flow[index] = (self.cell_center(next) - self.cell_center(index))
+                    .try_normalized()
+                    .unwrap_or_default();
+            }
+        }
+    }
+
+    /// Direction to follow from given position, `None` when there is no way
+    /// or position is already in target cell.
+    pub fn direction(&self, position: Vec2<f32>) -> Option<Vec2<f32>> {
+        let index = self.cell(position)?;
+        if Some(index) == self.target {
+            return None;
+        }
+        if self.blocked[index] {
+            // pushed into blocked cell - head out to the best reachable neighbour.
+            let next = self.escape(index)?;
+            return (self.cell_center(next) - position).try_normalized();
+        }
+        if self.costs[index] == u32::MAX {
+            return None;
+        }
+        Some(self.flow[index]).filter(|direction| !direction.is_approx_zero())
+    }
+
+    fn escape(&self, index: usize) -> Option<usize> {
+        let size = self.size as isize;
+        let (x, y) = ((index % self.size) as isize, (index / self.size) as isize);
+        (-1..=1)
+            .flat_map(|dy| (-1..=1).map(move |dx| (x + dx, y + dy)))
+            .filter(|(x, y)| *x >= 0 && *y >= 0 && *x < size && *y < size)
+            .map(|(x, y)| (y * size + x) as usize)
+            .filter(|index| self.costs[*index] != u32::MAX)
+            .min_by_key(|index| self.costs[*index])
+    }
+
+    // Free neighbour cells with cost of stepping there, diagonal steps cannot
+    // cut corners of blocked cells.
+    fn neighbours(&self, index: usize) -> impl Iterator<Item = (usize, u32)> + '_ {
+        let size = self.size as isize;
+        let (x, y) = ((index % self.size) as isize, (index / self.size) as isize);
+        let free = move |x: isize, y: isize| {
+            x >= 0 && y >= 0 && x < size && y < size && !self.blocked[(y * size + x) as usize]
+        };
+        [
+            (-1, 0),
+            (1, 0),
+            (0, -1),
+            (0, 1),
+            (-1, -1),
+            (1, -1),
+            (-1, 1),
+            (1, 1),
+        ]
+        .into_iter()
+        .filter(move |(dx, dy)| {
+            free(x + dx, y + dy) && (*dx == 0 || *dy == 0 || (free(x + dx, y) && free(x, y + dy)))
+        })
+        .map(move |(dx, dy)| {
+            let step = if dx == 0 || dy == 0 {
+                STRAIGHT_COST
+            } else {
+                DIAGONAL_COST
+            };
+            (((y + dy) * size + x + dx) as usize, step)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flow_goes_around_walls() {
+        let mut grid = NavigationGrid::new(NavigationData {
+            half_extent: 500.0,
+            cell_size: 50.0,
+            wall_lifetime: 1.0,
+        });
+        // vertical wall between enemy on the left and player on the right.
+        let wall = SpaceObject {
+            entity: None,
+            position: Vec2::new(0.0, -100.0),
+            collider: SpaceCollider::oriented_box(Vec2::new(20.0, 300.0), 0.0),
+        };
+        assert!(grid.set_blocked([wall.clone()]));
+        assert!(!grid.set_blocked([wall]));
+        assert!(grid.is_blocked(Vec2::new(0.0, 0.0)));
+        assert!(!grid.is_blocked(Vec2::new(0.0, 300.0)));
+
+        let player = Vec2::new(200.0, 0.0);
+        grid.compute_flow(player);
+        assert_eq!(grid.target(), grid.cell(player));
+        assert_eq!(grid.direction(player), None);
+        assert_eq!(grid.direction(Vec2::new(2000.0, 0.0)), None);
+
+        // walking along the field gets around the wall.
+        let mut position = Vec2::new(-200.0, 0.0);
+        for _ in 0..100 {
+            let Some(direction) = grid.direction(position) else {
+                break;
+            };
+            position += direction * 10.0;
+            assert!(!grid.is_blocked(position), "{:?}", position);
+        }
+        assert_eq!(grid.cell(position), grid.cell(player));
+
+        // enemy pushed into blocked cell finds its way out.
+        let position = Vec2::new(-10.0, 0.0);
+        assert!(grid.is_blocked(position));
+        assert!(grid.direction(position).unwrap().x < 0.0);
+
+        // walled in player cannot be reached.
+        grid.set_blocked((0..16).map(|index| {
+            let angle = std::f32::consts::TAU * index as f32 / 16.0;
+            SpaceObject {
+                entity: None,
+                position: player + Vec2::new(angle.cos(), angle.sin()) * 120.0,
+                collider: SpaceCollider::circle(30.0),
+            }
+        }));
+        grid.compute_flow(player);
+        assert_eq!(grid.direction(Vec2::new(-200.0, 0.0)), None);
+    }
+}
@@ -1,13 +1,9 @@
 use hecs::Entity;
 use micro_games_kit::third_party::{
-    raui_core::{Managed, ManagedRef, ManagedRefMut},
     rstar::{Envelope, Point, PointDistance, RTree, RTreeObject, AABB},
     vek::Vec2,
 };
-
-thread_local! {
-    static INSTANCE: Managed<Space> = Default::default();
-}
+use std::{collections::HashMap, f32::consts::FRAC_PI_2};
 
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub enum SpaceObjectId {
@@ -18,38 +14,327 @@ pub enum SpaceObjectId {
     Spell,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SpaceCollider {
+    Circle {
+        radius: f32,
+    },
+    // segment along local X axis, rounded by radius.
+    Capsule {
+        radius: f32,
+        half_length: f32,
+        rotation: f32,
+    },
+    // oriented box, rotation in radians.
+    Box {
+        half_extents: Vec2<f32>,
+        rotation: f32,
+    },
+}
+
+impl Default for SpaceCollider {
+    fn default() -> Self {
+        Self::Circle { radius: 0.0 }
+    }
+}
+
+impl SpaceCollider {
+    pub fn circle(radius: f32) -> Self {
+        Self::Circle { radius }
+    }
+
+    pub fn capsule(radius: f32, half_length: f32, direction: Vec2<f32>) -> Self {
+        Self::Capsule {
+            radius,
+            half_length,
+            rotation: direction.y.atan2(direction.x),
+        }
+    }
+
+    pub fn oriented_box(half_extents: Vec2<f32>, rotation: f32) -> Self {
+        Self::Box {
+            half_extents,
+            rotation,
+        }
+    }
+
+    pub fn set_radius(&mut self, value: f32) {
+        match self {
+            Self::Circle { radius } | Self::Capsule { radius, .. } => *radius = value,
+            Self::Box { .. } => {}
+        }
+    }
+
+    pub fn extents(&self) -> Vec2<f32> {
+        match *self {
+            Self::Circle { radius } => Vec2::broadcast(radius),
+            Self::Capsule {
+                radius,
+                half_length,
+                rotation,
+            } => axis(rotation).map(|v| v.abs()) * half_length + radius,
+            Self::Box {
+                half_extents,
+                rotation,
+            } => {
+                let (u, v) = (axis(rotation), axis(rotation).rotated_z(FRAC_PI_2));
+                u.map(|v| v.abs()) * half_extents.x + v.map(|v| v.abs()) * half_extents.y
+            }
+        }
+    }
+}
+
 #[derive(Debug, Default, Clone, PartialEq)]
 pub struct SpaceObject {
     pub entity: Option<Entity>,
     pub position: Vec2<f32>,
-    pub collider_radius: f32,
+    pub collider: SpaceCollider,
 }
 
 impl SpaceObject {
+    /// Tells if position and extents are finite, other objects can be neither
+    /// found in nor removed from the tree.
+    pub fn is_finite(&self) -> bool {
+        let extents = self.collider.extents();
+        [self.position.x, self.position.y, extents.x, extents.y]
+            .iter()
+            .all(|value| value.is_finite())
+    }
+
     pub fn does_collide_broad(&self, other: &Self) -> bool {
         self.envelope().intersects(&other.envelope())
     }
 
     pub fn does_collide_narrow(&self, other: &Self) -> bool {
-        let a = self.collider_radius * self.collider_radius;
-        let b = other.collider_radius * other.collider_radius;
-        self.position.distance_squared(other.position) <= a + b
+        use SpaceCollider::*;
+
+        match (self.collider, other.collider) {
+            (Circle { radius: a }, Circle { radius: b }) => {
+                self.position.distance_squared(other.position) <= (a + b) * (a + b)
+            }
+            (Circle { radius: a }, Capsule { radius: b, .. }) => {
+                let (from, to) = other.segment();
+                let point = closest_point_on_segment(self.position, from, to);
+                self.position.distance_squared(point) <= (a + b) * (a + b)
+            }
+            (Circle { radius }, Box { .. }) => {
+                let point = other.closest_point_in_box(self.position);
+                self.position.distance_squared(point) <= radius * radius
+            }
+            (Capsule { radius: a, .. }, Capsule { radius: b, .. }) => {
+                let (from_a, to_a) = self.segment();
+                let (from_b, to_b) = other.segment();
+                segment_segment_distance_squared(from_a, to_a, from_b, to_b) <= (a + b) * (a + b)
+            }
+            (Capsule { radius, .. }, Box { .. }) => {
+                let (from, to) = self.segment();
+                other.segment_box_distance_squared(from, to) <= radius * radius
+            }
+            (Box { .. }, Box { .. }) => self.does_box_overlap_box(other),
+            _ => other.does_collide_narrow(self),
+        }
+    }
+
+    /// Minimal translation that pushes this circle out of other object.
+    /// Returns `None` when objects do not overlap or this is not a circle.
+    pub fn contact(&self, other: &Self) -> Option<Vec2<f32>> {
+        let SpaceCollider::Circle { radius } = self.collider else {
+            return None;
+        };
+
+        let (point, radius) = match other.collider {
+            SpaceCollider::Circle {
+                radius: other_radius,
+            } => (other.position, radius + other_radius),
+            SpaceCollider::Capsule {
+                radius: other_radius,
+                ..
+            } => {
+                let (from, to) = other.segment();
+                (
+                    closest_point_on_segment(self.position, from, to),
+                    radius + other_radius,
+                )
+            }
+            SpaceCollider::Box { .. } => {
+                let point = other.closest_point_in_box(self.position);
+                if point.distance_squared(self.position) <= f32::EPSILON {
+                    // center is inside the box, push out along shallowest axis.
+                    let (u, v, half_extents) = other.box_axes();
+                    let local = self.position - other.position;
+                    let (x, y) = (local.dot(u), local.dot(v));
+                    let depth_x = half_extents.x - x.abs() + radius;
+                    let depth_y = half_extents.y - y.abs() + radius;
+                    return Some(if depth_x < depth_y {
+                        u * depth_x * x.signum()
+                    } else {
+                        v * depth_y * y.signum()
+                    });
+                }
+                (point, radius)
+            }
+        };
+
+        let difference = self.position - point;
+        let distance = difference.magnitude();
+        if distance >= radius {
+            return None;
+        }
+        let normal = if distance > f32::EPSILON {
+            difference / distance
+        } else {
+            Vec2::unit_x()
+        };
+        Some(normal * (radius - distance))
+    }
+
+    fn segment(&self) -> (Vec2<f32>, Vec2<f32>) {
+        match self.collider {
+            SpaceCollider::Capsule {
+                half_length,
+                rotation,
+                ..
+            } => {
+                let offset = axis(rotation) * half_length;
+                (self.position - offset, self.position + offset)
+            }
+            _ => (self.position, self.position),
+        }
+    }
+
+    fn box_axes(&self) -> (Vec2<f32>, Vec2<f32>, Vec2<f32>) {
+        match self.collider {
+            SpaceCollider::Box {
+                half_extents,
+                rotation,
+            } => {
+                let u = axis(rotation);
+                (u, u.rotated_z(FRAC_PI_2), half_extents)
+            }
+            _ => (Vec2::unit_x(), Vec2::unit_y(), Vec2::zero()),
+        }
+    }
+
+    fn box_corners(&self) -> [Vec2<f32>; 4] {
+        let (u, v, half_extents) = self.box_axes();
+        let u = u * half_extents.x;
+        let v = v * half_extents.y;
+        [
+            self.position - u - v,
+            self.position + u - v,
+            self.position + u + v,
+            self.position - u + v,
+        ]
+    }
+
+    fn closest_point_in_box(&self, point: Vec2<f32>) -> Vec2<f32> {
+        let (u, v, half_extents) = self.box_axes();
+        let local = point - self.position;
+        let x = local.dot(u).clamp(-half_extents.x, half_extents.x);
+        let y = local.dot(v).clamp(-half_extents.y, half_extents.y);
+        self.position + u * x + v * y
+    }
+
+    fn segment_box_distance_squared(&self, from: Vec2<f32>, to: Vec2<f32>) -> f32 {
+        let corners = self.box_corners();
+        let edges = [
+            (corners[0], corners[1]),
+            (corners[1], corners[2]),
+            (corners[2], corners[3]),
+            (corners[3], corners[0]),
+        ];
+        if edges
+            .iter()
+            .any(|(a, b)| do_segments_intersect(from, to, *a, *b))
+        {
+            return 0.0;
+        }
+        let endpoints = [from, to]
+            .into_iter()
+            .map(|point| point.distance_squared(self.closest_point_in_box(point)));
+        let corners = corners
+            .into_iter()
+            .map(|point| point.distance_squared(closest_point_on_segment(point, from, to)));
+        endpoints.chain(corners).fold(f32::INFINITY, f32::min)
+    }
+
+    fn does_box_overlap_box(&self, other: &Self) -> bool {
+        let (ua, va, _) = self.box_axes();
+        let (ub, vb, _) = other.box_axes();
+        let corners_a = self.box_corners();
+        let corners_b = other.box_corners();
+        [ua, va, ub, vb].into_iter().all(|axis| {
+            let (min_a, max_a) = project_points(&corners_a, axis);
+            let (min_b, max_b) = project_points(&corners_b, axis);
+            min_a <= max_b && min_b <= max_a
+        })
     }
 }
 
+fn axis(rotation: f32) -> Vec2<f32> {
+    let (y, x) = rotation.sin_cos();
+    Vec2 { x, y }
+}
+
+fn project_points(points: &[Vec2<f32>], axis: Vec2<f32>) -> (f32, f32) {
+    points
+        .iter()
+        .map(|point| point.dot(axis))
+        .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), value| {
+            (min.min(value), max.max(value))
+        })
+}
+
+fn closest_point_on_segment(point: Vec2<f32>, from: Vec2<f32>, to: Vec2<f32>) -> Vec2<f32> {
+    let segment = to - from;
+    let length_squared = segment.magnitude_squared();
+    if length_squared <= f32::EPSILON {
+        return from;
+    }
+    let factor = ((point - from).dot(segment) / length_squared).clamp(0.0, 1.0);
+    from + segment * factor
+}
+
+fn do_segments_intersect(a: Vec2<f32>, b: Vec2<f32>, c: Vec2<f32>, d: Vec2<f32>) -> bool {
+    fn cross(o: Vec2<f32>, a: Vec2<f32>, b: Vec2<f32>) -> f32 {
+        (a.x - o.x) * (b.y - o.y) - (a.y - o.y) * (b.x - o.x)
+    }
+
+    let d1 = cross(c, d, a);
+    let d2 = cross(c, d, b);
+    let d3 = cross(a, b, c);
+    let d4 = cross(a, b, d);
+    ((d1 > 0.0 && d2 < 0.0) || (d1 < 0.0 && d2 > 0.0))
+        && ((d3 > 0.0 && d4 < 0.0) || (d3 < 0.0 && d4 > 0.0))
+}
+
+fn segment_segment_distance_squared(
+    from_a: Vec2<f32>,
+    to_a: Vec2<f32>,
+    from_b: Vec2<f32>,
+    to_b: Vec2<f32>,
+) -> f32 {
+    if do_segments_intersect(from_a, to_a, from_b, to_b) {
+        return 0.0;
+    }
+    [
+        from_a.distance_squared(closest_point_on_segment(from_a, from_b, to_b)),
+        to_a.distance_squared(closest_point_on_segment(to_a, from_b, to_b)),
+        from_b.distance_squared(closest_point_on_segment(from_b, from_a, to_a)),
+        to_b.distance_squared(closest_point_on_segment(to_b, from_a, to_a)),
+    ]
+    .into_iter()
+    .fold(f32::INFINITY, f32::min)
+}
+
 impl RTreeObject for SpaceObject {
     type Envelope = AABB<[f32; 2]>;
 
     fn envelope(&self) -> Self::Envelope {
+        let extents = self.collider.extents();
         AABB::from_corners(
-            [
-                self.position.x - self.collider_radius,
-                self.position.y - self.collider_radius,
-            ],
-            [
-                self.position.x + self.collider_radius,
-                self.position.y + self.collider_radius,
-            ],
+            [self.position.x - extents.x, self.position.y - extents.y],
+            [self.position.x + extents.x, self.position.y + extents.y],
         )
     }
 }
@@ -66,19 +351,95 @@ impl PointDistance for SpaceObject {
 #[derive(Debug, Default)]
 pub struct Space {
     tree: RTree<SpaceObject>,
+    // last object state and update stamp per entity, used to detect changes.
+    objects: HashMap<Entity, (SpaceObject, usize)>,
+    stamp: usize,
 }
 
 impl Space {
-    pub fn read() -> ManagedRef<Self> {
-        INSTANCE.with(|instance| instance.lazy().borrow().unwrap())
+    /// Rebuilds whole tree from scratch.
+    /// Objects without entity or finite position are ignored, same as in
+    /// maintenance.
+    pub fn rebuild(&mut self, objects: Vec<SpaceObject>) {
+        self.stamp = self.stamp.wrapping_add(1);
+        self.objects = objects
+            .into_iter()
+            .filter(|object| object.is_finite())
+            .filter_map(|object| Some((object.entity?, (object, self.stamp))))
+            .collect();
+        self.tree = RTree::bulk_load(
+            self.objects
+                .values()
+                .map(|(object, _)| object.clone())
+                .collect(),
+        );
+    }
+
+    /// Updates only objects that were added, moved or changed since last
+    /// maintenance, and removes ones that were not reported anymore.
+    /// When most of the objects changed, bulk loading the tree is cheaper
+    /// than reinserting them one by one, so it falls back to that.
+    /// Objects without entity cannot be tracked and are ignored, so are ones
+    /// with non-finite position, which could never be removed again.
+    pub fn maintain(&mut self, objects: impl IntoIterator<Item = SpaceObject>) {
+        self.stamp = self.stamp.wrapping_add(1);
+        let stamp = self.stamp;
+        let mut to_remove = Vec::new();
+        let mut to_insert = Vec::new();
+
+        for object in objects {
+            let Some(entity) = object.entity.filter(|_| object.is_finite()) else {
+                continue;
+            };
+            match self.objects.get_mut(&entity) {
+                Some((current, current_stamp)) => {
+                    *current_stamp = stamp;
+                    if *current != object {
+                        to_remove.push(std::mem::replace(current, object.clone()));
+                        to_insert.push(object);
+                    }
+                }
+                None => {
+                    self.objects.insert(entity, (object.clone(), stamp));
+                    to_insert.push(object);
+                }
+            }
+        }
+
+        self.objects.retain(|_, (object, object_stamp)| {
+            if *object_stamp == stamp {
+                true
+            } else {
+                to_remove.push(std::mem::take(object));
+                false
+            }
+        });
+
+        // each moved object is both removed and inserted, so this rebuilds
+        // when roughly more than a third of objects moved.
+        if (to_remove.len() + to_insert.len()) * 3 > self.objects.len() * 2 {
+            self.tree = RTree::bulk_load(
+                self.objects
+                    .values()
+                    .map(|(object, _)| object.clone())
+                    .collect(),
+            );
+        } else {
+            for object in &to_remove {
+                self.tree.remove(object);
+            }
+            for object in to_insert {
+                self.tree.insert(object);
+            }
+        }
     }
 
-    pub fn write() -> ManagedRefMut<Self> {
-        INSTANCE.with(|instance| instance.lazy().borrow_mut().unwrap())
+    pub fn len(&self) -> usize {
+        self.tree.size()
     }
 
-    pub fn maintain(&mut self, objects: Vec<SpaceObject>) {
-        self.tree = RTree::bulk_load(objects);
+    pub fn is_empty(&self) -> bool {
+        self.tree.size() == 0
     }
 
     pub fn nearest(&self, position: Vec2<f32>) -> impl Iterator<Item = &SpaceObject> {
@@ -114,6 +475,23 @@ impl Space {
             .filter(move |item| !narrow || object.does_collide_narrow(item))
     }
 
+    /// Objects touched by circle of given radius moving along the segment.
+    pub fn sweep(
+        &self,
+        from: Vec2<f32>,
+        to: Vec2<f32>,
+        radius: f32,
+    ) -> impl Iterator<Item = &SpaceObject> {
+        let object = SpaceObject {
+            entity: None,
+            position: (from + to) * 0.5,
+            collider: SpaceCollider::capsule(radius, from.distance(to) * 0.5, to - from),
+        };
+        self.tree
+            .locate_in_envelope_intersecting(&object.envelope())
+            .filter(move |item| object.does_collide_narrow(item))
+    }
+
     // pub fn find_by_id(&self, id: SpaceObjectId) -> Option<&SpaceObject> {
     //     self.tree.iter().find(|object| object.id == id)
     // }
@@ -122,3 +500,512 @@ impl Space {
         self.tree.iter()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::FRAC_PI_4;
+
+    fn object(x: f32, y: f32, collider: SpaceCollider) -> SpaceObject {
+        SpaceObject {
+            entity: None,
+            position: Vec2::new(x, y),
+            collider,
+        }
+    }
+
+    fn assert_collision(a: &SpaceObject, b: &SpaceObject, expected: bool) {
+        assert_eq!(a.does_collide_narrow(b), expected, "{:?} vs {:?}", a, b);
+        assert_eq!(b.does_collide_narrow(a), expected, "{:?} vs {:?}", b, a);
+        if expected {
+            assert!(a.does_collide_broad(b), "{:?} vs {:?}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_circle_circle() {
+        let a = object(0.0, 0.0, SpaceCollider::circle(10.0));
+
+        // sum of radii is the contact distance, not their squared sum.
+        assert_collision(&a, &object(19.0, 0.0, SpaceCollider::circle(10.0)), true);
+        assert_collision(&a, &object(20.0, 0.0, SpaceCollider::circle(10.0)), true);
+        assert_collision(&a, &object(21.0, 0.0, SpaceCollider::circle(10.0)), false);
+        assert_collision(&a, &object(0.0, 0.0, SpaceCollider::circle(1.0)), true);
+        assert_collision(&a, &object(10.0, 10.0, SpaceCollider::circle(4.5)), true);
+        assert_collision(
+            &a,
+            &object(10.0, 10.0, SpaceCollider::circle(4.0 - 0.2)),
+            false,
+        );
+    }
+
+    #[test]
+    fn test_circle_capsule() {
+        let capsule = object(0.0, 0.0, SpaceCollider::capsule(5.0, 50.0, Vec2::unit_x()));
+
+        assert_collision(
+            &capsule,
+            &object(0.0, 14.0, SpaceCollider::circle(10.0)),
+            true,
+        );
+        assert_collision(
+            &capsule,
+            &object(0.0, 16.0, SpaceCollider::circle(10.0)),
+            false,
+        );
+        assert_collision(
+            &capsule,
+            &object(40.0, -14.0, SpaceCollider::circle(10.0)),
+            true,
+        );
+        assert_collision(
+            &capsule,
+            &object(64.0, 0.0, SpaceCollider::circle(10.0)),
+            true,
+        );
+        assert_collision(
+            &capsule,
+            &object(66.0, 0.0, SpaceCollider::circle(10.0)),
+            false,
+        );
+        assert_collision(
+            &capsule,
+            &object(62.0, 10.0, SpaceCollider::circle(10.0)),
+            false,
+        );
+
+        let capsule = object(0.0, 0.0, SpaceCollider::capsule(5.0, 50.0, Vec2::unit_y()));
+        assert_collision(
+            &capsule,
+            &object(0.0, 64.0, SpaceCollider::circle(10.0)),
+            true,
+        );
+        assert_collision(
+            &capsule,
+            &object(20.0, 0.0, SpaceCollider::circle(10.0)),
+            false,
+        );
+    }
+
+    #[test]
+    fn test_circle_box() {
+        let rectangle = object(
+            0.0,
+            0.0,
+            SpaceCollider::oriented_box(Vec2::new(20.0, 10.0), 0.0),
+        );
+
+        assert_collision(
+            &rectangle,
+            &object(0.0, 0.0, SpaceCollider::circle(1.0)),
+            true,
+        );
+        assert_collision(
+            &rectangle,
+            &object(29.0, 0.0, SpaceCollider::circle(10.0)),
+            true,
+        );
+        assert_collision(
+            &rectangle,
+            &object(31.0, 0.0, SpaceCollider::circle(10.0)),
+            false,
+        );
+        assert_collision(
+            &rectangle,
+            &object(0.0, 19.0, SpaceCollider::circle(10.0)),
+            true,
+        );
+        assert_collision(
+            &rectangle,
+            &object(0.0, 21.0, SpaceCollider::circle(10.0)),
+            false,
+        );
+        // near the corner the rounded distance matters, not the AABB.
+        assert_collision(
+            &rectangle,
+            &object(28.0, 18.0, SpaceCollider::circle(10.0)),
+            false,
+        );
+        assert_collision(
+            &rectangle,
+            &object(26.0, 16.0, SpaceCollider::circle(10.0)),
+            true,
+        );
+
+        let diamond = object(
+            0.0,
+            0.0,
+            SpaceCollider::oriented_box(Vec2::new(10.0, 10.0), FRAC_PI_4),
+        );
+        assert_collision(
+            &diamond,
+            &object(14.0, 0.0, SpaceCollider::circle(1.0)),
+            true,
+        );
+        assert_collision(
+            &diamond,
+            &object(10.0, 10.0, SpaceCollider::circle(1.0)),
+            false,
+        );
+    }
+
+    #[test]
+    fn test_capsule_capsule() {
+        let a = object(0.0, 0.0, SpaceCollider::capsule(5.0, 50.0, Vec2::unit_x()));
+
+        // crossing segments.
+        assert_collision(
+            &a,
+            &object(0.0, 0.0, SpaceCollider::capsule(1.0, 50.0, Vec2::unit_y())),
+            true,
+        );
+        // parallel, touching sides.
+        assert_collision(
+            &a,
+            &object(30.0, 9.0, SpaceCollider::capsule(5.0, 50.0, Vec2::unit_x())),
+            true,
+        );
+        assert_collision(
+            &a,
+            &object(
+                30.0,
+                11.0,
+                SpaceCollider::capsule(5.0, 50.0, Vec2::unit_x()),
+            ),
+            false,
+        );
+        // collinear, touching caps.
+        assert_collision(
+            &a,
+            &object(
+                109.0,
+                0.0,
+                SpaceCollider::capsule(5.0, 50.0, Vec2::unit_x()),
+            ),
+            true,
+        );
+        assert_collision(
+            &a,
+            &object(
+                111.0,
+                0.0,
+                SpaceCollider::capsule(5.0, 50.0, Vec2::unit_x()),
+            ),
+            false,
+        );
+        // T shape, cap against side.
+        assert_collision(
+            &a,
+            &object(0.0, 59.0, SpaceCollider::capsule(5.0, 50.0, Vec2::unit_y())),
+            true,
+        );
+        assert_collision(
+            &a,
+            &object(0.0, 61.0, SpaceCollider::capsule(5.0, 50.0, Vec2::unit_y())),
+            false,
+        );
+    }
+
+    #[test]
+    fn test_capsule_box() {
+        let rectangle = object(
+            0.0,
+            0.0,
+            SpaceCollider::oriented_box(Vec2::new(20.0, 10.0), 0.0),
+        );
+
+        // segment passing through the box without endpoints inside.
+        assert_collision(
+            &rectangle,
+            &object(0.0, 0.0, SpaceCollider::capsule(1.0, 100.0, Vec2::unit_y())),
+            true,
+        );
+        // fully contained.
+        assert_collision(
+            &rectangle,
+            &object(0.0, 0.0, SpaceCollider::capsule(1.0, 5.0, Vec2::unit_x())),
+            true,
+        );
+        // parallel above the box.
+        assert_collision(
+            &rectangle,
+            &object(0.0, 14.0, SpaceCollider::capsule(5.0, 50.0, Vec2::unit_x())),
+            true,
+        );
+        assert_collision(
+            &rectangle,
+            &object(0.0, 16.0, SpaceCollider::capsule(5.0, 50.0, Vec2::unit_x())),
+            false,
+        );
+        // box corner poking the capsule side.
+        let diamond = object(
+            0.0,
+            0.0,
+            SpaceCollider::oriented_box(Vec2::new(10.0, 10.0), FRAC_PI_4),
+        );
+        let corner = 10.0 * std::f32::consts::SQRT_2;
+        assert_collision(
+            &diamond,
+            &object(
+                0.0,
+                corner + 4.0,
+                SpaceCollider::capsule(5.0, 50.0, Vec2::unit_x()),
+            ),
+            true,
+        );
+        assert_collision(
+            &diamond,
+            &object(
+                0.0,
+                corner + 6.0,
+                SpaceCollider::capsule(5.0, 50.0, Vec2::unit_x()),
+            ),
+            false,
+        );
+    }
+
+    #[test]
+    fn test_box_box() {
+        let a = object(
+            0.0,
+            0.0,
+            SpaceCollider::oriented_box(Vec2::new(10.0, 10.0), 0.0),
+        );
+
+        assert_collision(
+            &a,
+            &object(
+                19.0,
+                0.0,
+                SpaceCollider::oriented_box(Vec2::new(10.0, 10.0), 0.0),
+            ),
+            true,
+        );
+        assert_collision(
+            &a,
+            &object(
+                21.0,
+                0.0,
+                SpaceCollider::oriented_box(Vec2::new(10.0, 10.0), 0.0),
+            ),
+            false,
+        );
+        // rotated box whose AABB overlaps but the shapes do not.
+        let corner = 10.0 * std::f32::consts::SQRT_2;
+        assert_collision(
+            &a,
+            &object(
+                10.0 + corner - 1.0,
+                10.0 + corner - 1.0,
+                SpaceCollider::oriented_box(Vec2::new(10.0, 10.0), FRAC_PI_4),
+            ),
+            false,
+        );
+        assert_collision(
+            &a,
+            &object(
+                10.0 + corner - 1.0,
+                0.0,
+                SpaceCollider::oriented_box(Vec2::new(10.0, 10.0), FRAC_PI_4),
+            ),
+            true,
+        );
+    }
+
+    #[test]
+    fn test_circle_contact() {
+        let circle = object(15.0, 0.0, SpaceCollider::circle(10.0));
+
+        let push = circle
+            .contact(&object(0.0, 0.0, SpaceCollider::circle(10.0)))
+            .unwrap();
+        assert!((push - Vec2::new(5.0, 0.0)).magnitude() < 1.0e-4);
+        assert!(circle
+            .contact(&object(-10.0, 0.0, SpaceCollider::circle(10.0)))
+            .is_none());
+
+        let push = circle
+            .contact(&object(
+                5.0,
+                20.0,
+                SpaceCollider::capsule(5.0, 50.0, Vec2::unit_y()),
+            ))
+            .unwrap();
+        assert!((push - Vec2::new(5.0, 0.0)).magnitude() < 1.0e-4);
+
+        let rectangle = object(
+            0.0,
+            0.0,
+            SpaceCollider::oriented_box(Vec2::new(10.0, 10.0), 0.0),
+        );
+        let push = circle.contact(&rectangle).unwrap();
+        assert!((push - Vec2::new(5.0, 0.0)).magnitude() < 1.0e-4);
+        // center inside the box gets pushed along the shallowest axis.
+        let push = object(0.0, 8.0, SpaceCollider::circle(1.0))
+            .contact(&rectangle)
+            .unwrap();
+        assert!((push - Vec2::new(0.0, 3.0)).magnitude() < 1.0e-4);
+
+        assert!(
+            object(0.0, 0.0, SpaceCollider::capsule(1.0, 1.0, Vec2::unit_x()))
+                .contact(&rectangle)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_collider_envelope() {
+        let capsule = object(0.0, 0.0, SpaceCollider::capsule(5.0, 50.0, Vec2::unit_y()));
+        let envelope = capsule.envelope();
+        assert!((envelope.lower()[0] + 5.0).abs() < 1.0e-4);
+        assert!((envelope.lower()[1] + 55.0).abs() < 1.0e-4);
+        assert!((envelope.upper()[0] - 5.0).abs() < 1.0e-4);
+        assert!((envelope.upper()[1] - 55.0).abs() < 1.0e-4);
+
+        let diamond = object(
+            0.0,
+            0.0,
+            SpaceCollider::oriented_box(Vec2::new(10.0, 10.0), FRAC_PI_4),
+        );
+        let corner = 10.0 * std::f32::consts::SQRT_2;
+        let envelope = diamond.envelope();
+        assert!((envelope.upper()[0] - corner).abs() < 1.0e-4);
+        assert!((envelope.upper()[1] - corner).abs() < 1.0e-4);
+    }
+}
+
+#[cfg(test)]
+mod maintenance_tests {
+    use super::*;
+    use hecs::World;
+    use micro_games_kit::third_party::{
+        rand::{rngs::StdRng, Rng, SeedableRng},
+        Instant,
+    };
+
+    fn spawn_objects(count: usize, world: &mut World, rng: &mut StdRng) -> Vec<SpaceObject> {
+        (0..count)
+            .map(|_| SpaceObject {
+                entity: Some(world.spawn(())),
+                position: Vec2::new(
+                    rng.gen_range(-3500.0..=3500.0),
+                    rng.gen_range(-3500.0..=3500.0),
+                ),
+                collider: SpaceCollider::circle(30.0),
+            })
+            .collect()
+    }
+
+    fn probe(position: Vec2<f32>) -> SpaceObject {
+        SpaceObject {
+            entity: None,
+            position,
+            collider: SpaceCollider::circle(1.0),
+        }
+    }
+
+    #[test]
+    fn test_space_maintain() {
+        let mut world = World::new();
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut objects = spawn_objects(3, &mut world, &mut rng);
+        objects[0].position = Vec2::new(0.0, 0.0);
+        objects[1].position = Vec2::new(100.0, 0.0);
+        objects[2].position = Vec2::new(200.0, 0.0);
+
+        let mut space = Space::default();
+        space.maintain(objects.iter().cloned());
+        assert_eq!(space.len(), 3);
+
+        // moved object is found only at its new position.
+        objects[0].position = Vec2::new(0.0, 100.0);
+        space.maintain(objects.iter().cloned());
+        assert_eq!(space.len(), 3);
+        let query = probe(Vec2::new(0.0, 0.0));
+        assert_eq!(space.collisions(&query, true).count(), 0);
+        let query = probe(Vec2::new(0.0, 100.0));
+        assert_eq!(
+            space
+                .collisions(&query, true)
+                .map(|object| object.entity)
+                .collect::<Vec<_>>(),
+            vec![objects[0].entity]
+        );
+
+        // objects not reported anymore are removed.
+        let removed = objects.remove(1);
+        space.maintain(objects.iter().cloned());
+        assert_eq!(space.len(), 2);
+        assert_eq!(space.collisions(&probe(removed.position), true).count(), 0);
+
+        // rebuild and maintain agree on contents, untracked objects included.
+        objects.push(probe(Vec2::new(300.0, 0.0)));
+        space.maintain(objects.iter().cloned());
+        let mut rebuilt = Space::default();
+        rebuilt.rebuild(objects.clone());
+        assert_eq!(rebuilt.len(), space.len());
+        let mut a = space.iter().map(|object| object.entity).collect::<Vec<_>>();
+        let mut b = rebuilt
+            .iter()
+            .map(|object| object.entity)
+            .collect::<Vec<_>>();
+        a.sort();
+        b.sort();
+        assert_eq!(a, b);
+
+        // object that got non-finite position drops out and does not linger.
+        objects[0].position = Vec2::broadcast(f32::NAN);
+        space.maintain(objects.iter().cloned());
+        assert_eq!(space.len(), 1);
+        objects[0].position = Vec2::new(0.0, 100.0);
+        space.maintain(objects.iter().cloned());
+        assert_eq!(space.len(), 2);
+        objects[0].position = Vec2::broadcast(f32::NAN);
+        rebuilt.rebuild(objects.clone());
+        assert_eq!(rebuilt.len(), 1);
+    }
+
+    // cargo test --release -- --ignored --nocapture bench_space_maintenance
+    #[test]
+    #[ignore]
+    fn bench_space_maintenance() {
+        const TICKS: usize = 100;
+
+        println!("| colliders | moving | rebuild ms/tick | maintain ms/tick |");
+        for count in [100, 1_000, 10_000] {
+            for moving_percentage in [10, 100] {
+                let mut world = World::new();
+                let mut rng = StdRng::seed_from_u64(0);
+                let mut objects = spawn_objects(count, &mut world, &mut rng);
+                let moving = count * moving_percentage / 100;
+                let mut rebuild_space = Space::default();
+                let mut maintain_space = Space::default();
+                let mut rebuild_time = 0.0;
+                let mut maintain_time = 0.0;
+
+                for _ in 0..TICKS {
+                    for object in objects.iter_mut().take(moving) {
+                        object.position +=
+                            Vec2::new(rng.gen_range(-5.0..=5.0), rng.gen_range(-5.0..=5.0));
+                    }
+
+                    let timer = Instant::now();
+                    rebuild_space.rebuild(objects.clone());
+                    rebuild_time += timer.elapsed().as_secs_f64();
+
+                    let timer = Instant::now();
+                    maintain_space.maintain(objects.iter().cloned());
+                    maintain_time += timer.elapsed().as_secs_f64();
+                }
+
+                println!(
+                    "| {} | {}% | {:.4} | {:.4} |",
+                    count,
+                    moving_percentage,
+                    rebuild_time * 1000.0 / TICKS as f64,
+                    maintain_time * 1000.0 / TICKS as f64,
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,23 @@
+use crate::game::{
+    components::{collidable::Collidable, player::Player},
+    utils::space::{SpaceCollider, SpaceObject},
+};
+
+/// Collidable of given shape, collision detector fills in its entity and
+/// position.
+pub fn collidable(collider: SpaceCollider) -> Collidable {
+    Collidable {
+        space_object: Some(SpaceObject {
+            collider,
+            ..Default::default()
+        }),
+    }
+}
+
+/// Player as gameplay spawns it.
+pub fn player() -> Player {
+    Player {
+        current_effect_particle_time: 1.0,
+        current_effect_particle_accumulator: 0.0,
+    }
+}
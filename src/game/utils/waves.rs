@@ -0,0 +1,100 @@
+use crate::game::utils::magic::spell_tag::SpellTagEffect;
+use micro_games_kit::third_party::{serde::Deserialize, toml};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(crate = "micro_games_kit::third_party::serde")]
+pub struct ElementWeights {
+    #[serde(default)]
+    pub none: u32,
+    #[serde(default)]
+    pub fire: u32,
+    #[serde(default)]
+    pub water: u32,
+    #[serde(default)]
+    pub electric: u32,
+}
+
+impl ElementWeights {
+    pub fn weight(&self, effect: SpellTagEffect) -> u32 {
+        match effect {
+            SpellTagEffect::None => self.none,
+            SpellTagEffect::Fire => self.fire,
+            SpellTagEffect::Water => self.water,
+            SpellTagEffect::Electric => self.electric,
+        }
+    }
+
+    pub fn to_weights(&self) -> Vec<(SpellTagEffect, u32)> {
+        vec![
+            (SpellTagEffect::None, self.none),
+            (SpellTagEffect::Fire, self.fire),
+            (SpellTagEffect::Water, self.water),
+            (SpellTagEffect::Electric, self.electric),
+        ]
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(crate = "micro_games_kit::third_party::serde")]
+pub struct WaveData {
+    pub duration: f32,
+    pub spawn_interval: f32,
+    pub count_limit: usize,
+    pub total: usize,
+    pub enemies: BTreeMap<String, u32>,
+    pub elements: ElementWeights,
+    /// Enemy kind spawned once when wave starts.
+    #[serde(default)]
+    pub boss: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(crate = "micro_games_kit::third_party::serde")]
+pub struct EndlessScaling {
+    pub spawn_interval_multiplier: f32,
+    pub spawn_interval_min: f32,
+    pub count_limit_increment: usize,
+    pub total_increment: usize,
+    pub duration_increment: f32,
+    /// Every this many endless waves bring the boss of last wave back, 0 means never.
+    #[serde(default)]
+    pub boss_interval: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(crate = "micro_games_kit::third_party::serde")]
+pub struct WaveScript {
+    pub waves: Vec<WaveData>,
+    pub endless: EndlessScaling,
+}
+
+impl WaveScript {
+    pub fn load() -> Self {
+        Self::load_from_str(include_str!("../../../assets/data/waves.toml"))
+            .expect("Could not load wave script!")
+    }
+
+    pub fn load_from_str(content: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(content)
+    }
+
+    /// Wave at given index, waves past the script are its last wave scaled up.
+    pub fn wave(&self, index: usize) -> Option<WaveData> {
+        if let Some(wave) = self.waves.get(index) {
+            return Some(wave.clone());
+        }
+        let mut wave = self.waves.last()?.clone();
+        let level = index + 1 - self.waves.len();
+        wave.duration += self.endless.duration_increment * level as f32;
+        wave.spawn_interval = (wave.spawn_interval
+            * self.endless.spawn_interval_multiplier.powi(level as i32))
+        .max(self.endless.spawn_interval_min);
+        wave.count_limit += self.endless.count_limit_increment * level;
+        wave.total += self.endless.total_increment * level;
+        if !level.is_multiple_of(self.endless.boss_interval) {
+            wave.boss = None;
+        }
+        Some(wave)
+    }
+}